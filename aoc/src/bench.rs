@@ -0,0 +1,105 @@
+use common::{format_duration, Solution};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let mid = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[mid - 1] + sorted[mid]) / 2,
+            _ => sorted[mid],
+        };
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+        })
+    }
+}
+
+/// Timings for every step of one day, collected over repeated runs.
+pub struct Samples {
+    day: u8,
+    steps: Vec<(String, Vec<Duration>)>,
+}
+
+impl Samples {
+    pub fn new(day: u8) -> Self {
+        Samples { day, steps: vec![] }
+    }
+
+    pub fn add(&mut self, solution: &Solution) {
+        self.record("parse", solution.parse_elapsed);
+        for result in &solution.parts {
+            self.record(&format!("part {}", result.part), result.elapsed);
+        }
+        self.record("total", solution.total_elapsed());
+    }
+
+    fn record(&mut self, step: &str, elapsed: Duration) {
+        match self.steps.iter_mut().find(|(name, _)| name == step) {
+            Some((_, samples)) => samples.push(elapsed),
+            None => self.steps.push((step.to_string(), vec![elapsed])),
+        }
+    }
+}
+
+pub fn print_table(days: &[Samples]) {
+    println!(
+        "{:>3}  {:<8} {:>6} {:>10} {:>10} {:>10}",
+        "day", "step", "runs", "min", "median", "mean"
+    );
+
+    for samples in days {
+        for (step, durations) in &samples.steps {
+            if let Some(stats) = Stats::of(durations) {
+                println!(
+                    "{:>3}  {:<8} {:>6} {:>10} {:>10} {:>10}",
+                    samples.day,
+                    step,
+                    durations.len(),
+                    format_duration(stats.min),
+                    format_duration(stats.median),
+                    format_duration(stats.mean)
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn computes_min_median_and_mean() {
+        let millis = |ms: &[u64]| -> Vec<Duration> {
+            ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+        };
+
+        let odd = Stats::of(&millis(&[5, 1, 3])).unwrap();
+        let even = Stats::of(&millis(&[4, 1, 2, 9])).unwrap();
+
+        assert_eq!(Duration::from_millis(1), odd.min);
+        assert_eq!(Duration::from_millis(3), odd.median);
+        assert_eq!(Duration::from_millis(3), odd.mean);
+        assert_eq!(Duration::from_millis(3), even.median);
+        assert_eq!(Duration::from_millis(4), even.mean);
+        assert_eq!(None, Stats::of(&[]));
+    }
+}
@@ -0,0 +1,42 @@
+use common::answers::{self, Check};
+use common::{AnswerKey, AnswerStore, Error, Solution};
+
+pub struct Checker {
+    store: AnswerStore,
+    failures: usize,
+}
+
+impl Checker {
+    pub fn load(path: &str) -> Result<Self, Error> {
+        Ok(Checker {
+            store: AnswerStore::load(path)?,
+            failures: 0,
+        })
+    }
+
+    pub fn report(&mut self, input: &str, solution: &Solution) {
+        let day = solution.day;
+        for result in &solution.parts {
+            let (part, answer) = (result.part, &result.answer);
+            let key = AnswerKey::new(day, input, part);
+
+            match self.store.check(&key, answer) {
+                Check::Pass => println!("PASS day {} part {}", day, part),
+                Check::Unknown => {
+                    println!("SKIP day {} part {} (no recorded answer)", day, part)
+                }
+                Check::Fail { expected } => {
+                    self.failures += 1;
+                    println!("FAIL day {} part {}", day, part);
+                    for line in answers::diff(&expected, answer) {
+                        println!("    {}", line);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures == 0
+    }
+}
@@ -0,0 +1,648 @@
+use bench::Samples;
+use check::Checker;
+use common::answers::ANSWERS_PATH;
+use common::cli::{self, InputArg};
+use common::generate;
+use common::report::{self, Format};
+use common::{Answer, AnswerKey, Args, Client, Error, InputSource, Puzzle, Solution};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use watch::Watcher;
+
+mod bench;
+mod check;
+mod pool;
+mod scaffold;
+mod submit;
+mod watch;
+
+const USAGE: &str = "usage:
+    aoc run <days> [options] [input]
+    aoc validate <days> [options] [input]
+    aoc list
+    aoc fetch <days>
+    aoc submit <day> <part> [answer]
+    aoc scaffold <day>
+    aoc watch <day> [--example] [input]
+    aoc generate <day> <size> [seed]
+
+<days> is a single day (10), a range (1-4) or a comma separated list (1,3,10),
+or `all`. The input defaults to inputs/d<day>-p1.txt and can only be given
+for a single day.
+
+validate checks each input strictly without solving it and lists every line
+that does not match the puzzle's format, exiting non-zero if any do.
+
+fetch downloads missing inputs into inputs/d<day>-p1.txt using the session
+token from AOC_SESSION or aoc.toml, inputs already there are never refetched.
+
+submit posts an answer, solving the day's input when no answer is given. The
+verdict is recorded in answers.toml and answers already known to be wrong are
+not sent again.
+
+generate prints a random input in the day's format, the same seed always gives
+the same input. <size> counts elves, rounds, rucksack groups, pairs,
+instructions or monkeys depending on the day.
+
+watch re-runs a day's tests and solver whenever its crate, common/src or its
+inputs change. Both the example and the input are solved, or only the example
+with --example.
+
+run options:
+    --check             compare the answers against answers.toml and exit
+                        non-zero if any of them changed
+    --bench <runs>      solve each day <runs> times and print a timing table
+    --jobs <n>          solve up to <n> days and parts at once, answers are still
+                        printed in day order";
+
+fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::of::<day1::Day1>(),
+        Puzzle::of::<day2::Day2>(),
+        Puzzle::of::<day3::Day3>(),
+        Puzzle::of::<day4::Day4>(),
+        Puzzle::of::<day10::Day10>(),
+        Puzzle::of::<day11::Day11>(),
+    ]
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(args[1..].to_vec()),
+        Some("validate") => validate(args[1..].to_vec()),
+        Some("list") => {
+            list();
+            Ok(true)
+        }
+        Some("fetch") => fetch(&args[1..]).map(|()| true),
+        Some("submit") => submit(&args[1..]),
+        Some("generate") => generate(&args[1..]).map(|()| true),
+        Some("scaffold") => scaffold(&args[1..]).map(|()| true),
+        Some("watch") => watch(args[1..].to_vec()).map(|()| true),
+        _ => Err(Error::argument("no command given")),
+    };
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            if let Error::Argument(_) = error {
+                eprintln!("\n{}", USAGE);
+            }
+            process::exit(error.exit_code());
+        }
+    }
+}
+
+fn fetch(args: &[String]) -> Result<(), Error> {
+    let [spec] = args else {
+        return Err(Error::argument("fetch takes exactly one <days> argument"));
+    };
+    let days = parse_days(spec)?;
+    let client = Client::from_env()?;
+
+    for day in days {
+        let path = client.input_path(day);
+        let cached = path.metadata().is_ok_and(|meta| meta.len() > 0);
+
+        client.input(day)?;
+        if cached {
+            println!("day {}: already in {}", day, path.display());
+        } else {
+            println!("day {}: fetched into {}", day, path.display());
+        }
+    }
+    Ok(())
+}
+
+fn submit(args: &[String]) -> Result<bool, Error> {
+    let (day, part, answer) = match args {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer)),
+        _ => {
+            return Err(Error::argument(
+                "submit takes a day, a part and optionally the answer",
+            ))
+        }
+    };
+    let day = parse_day(day)?;
+    let part = match cli::parse_parts(part)?.as_slice() {
+        [part] => *part,
+        _ => return Err(Error::argument("submit takes part 1 or 2")),
+    };
+
+    let input = cli::default_input_path(day);
+    let answer = match answer {
+        Some(answer) => answer
+            .parse()
+            .map(Answer::Number)
+            .unwrap_or_else(|_| Answer::Text(answer.clone())),
+        None => {
+            let puzzle = puzzles()
+                .into_iter()
+                .find(|p| p.day == day)
+                .ok_or_else(|| Error::argument(format!("day {} has no solver", day)))?;
+            let solution = puzzle.solve_source(InputSource::path(input.clone()), &[part])?;
+            solution.parts[0].answer.clone()
+        }
+    };
+    if answer.to_string().contains('\n') {
+        return Err(Error::argument(format!(
+            "day {} part {} is a picture, read the answer from it and pass it to submit",
+            day, part
+        )));
+    }
+
+    let client = Client::from_env()?;
+    submit::submit(
+        &client,
+        ANSWERS_PATH,
+        AnswerKey::new(day, input, part),
+        answer,
+    )
+}
+
+fn generate(args: &[String]) -> Result<(), Error> {
+    let (day, size, seed) = match args {
+        [day, size] => (day, size, None),
+        [day, size, seed] => (day, size, Some(seed)),
+        _ => {
+            return Err(Error::argument(
+                "generate takes a day, a size and optionally a seed",
+            ))
+        }
+    };
+    let day = parse_day(day)?;
+    let size = size
+        .parse::<usize>()
+        .map_err(|_| Error::argument(format!("invalid size `{}`", size)))?;
+    let seed = match seed {
+        Some(seed) => seed
+            .parse::<u64>()
+            .map_err(|_| Error::argument(format!("invalid seed `{}`", seed)))?,
+        None => 0,
+    };
+    if day == 11 && !(2..=generate::MAX_MONKEYS).contains(&size) {
+        return Err(Error::argument(format!(
+            "day 11 needs between 2 and {} monkeys",
+            generate::MAX_MONKEYS
+        )));
+    }
+
+    let input = generate::input(day, size, seed)
+        .ok_or_else(|| Error::argument(format!("day {} has no generator", day)))?;
+    print!("{}", input);
+    Ok(())
+}
+
+fn scaffold(args: &[String]) -> Result<(), Error> {
+    let [day] = args else {
+        return Err(Error::argument("scaffold takes exactly one day"));
+    };
+    let day = parse_day(day)?;
+
+    let root = env::current_dir().map_err(|error| Error::from_io(".", error))?;
+    if !root.join("aoc").is_dir() {
+        return Err(Error::argument(
+            "scaffold must be run from the workspace root",
+        ));
+    }
+
+    scaffold::scaffold(&root, day)?;
+    println!(
+        "created day{}, add the puzzle input to inputs/d{}-p1.txt or run `aoc fetch {}`",
+        day, day, day
+    );
+    Ok(())
+}
+
+/// Runs until interrupted, only returning early when the arguments are wrong.
+fn watch(args: Vec<String>) -> Result<(), Error> {
+    let mut args = Args::parse(args)?;
+    if args.help {
+        println!("{}\n\n{}", USAGE, cli::OPTIONS_HELP);
+        return Ok(());
+    }
+
+    let day = match std::mem::take(&mut args.positional).as_slice() {
+        [day] => parse_day(day)?,
+        [day, path] => {
+            args.set_input(InputArg::from_arg(path.clone()))?;
+            parse_day(day)?
+        }
+        _ => {
+            return Err(Error::argument(
+                "watch takes a day and optionally an input path",
+            ))
+        }
+    };
+    if !puzzles().iter().any(|p| p.day == day) {
+        return Err(Error::argument(format!("day {} has no solver", day)));
+    }
+    if !Path::new(&format!("day{}", day)).is_dir() {
+        return Err(Error::argument("watch must be run from the workspace root"));
+    }
+
+    // Each input is shown under a label, solved with the day binary's arguments
+    let example = (
+        "example",
+        vec!["--example".to_string()],
+        cli::example_input_path(day),
+    );
+    let input = |path: String| ("input", vec![path.clone()], path);
+    let inputs = match &args.input {
+        InputArg::Default => vec![example, input(cli::default_input_path(day))],
+        InputArg::Example => vec![example],
+        InputArg::Path(path) => vec![example, input(path.clone())],
+        InputArg::Stdin => {
+            return Err(Error::argument(
+                "watch reads its input again on every change, so it cannot read stdin",
+            ))
+        }
+    };
+
+    let mut paths = vec![
+        PathBuf::from(format!("day{}", day)),
+        PathBuf::from("common/src"),
+    ];
+    paths.extend(inputs.iter().map(|(_, _, path)| PathBuf::from(path)));
+    let runs: Vec<(String, Vec<String>)> = inputs
+        .into_iter()
+        .map(|(label, args, _)| (label.to_string(), args))
+        .collect();
+
+    let watched: Vec<String> = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    println!("day {}: watching {}", day, watched.join(", "));
+    let mut watcher = Watcher::new(paths);
+    watch::check(day, &runs);
+
+    loop {
+        let changed: Vec<String> = watcher
+            .wait()
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        println!("\nday {}: {} changed", day, changed.join(", "));
+        watch::check(day, &runs);
+    }
+}
+
+/// Removes a runner-only flag from the arguments, before the rest go to `Args::parse`.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/// Removes a runner-only option and its value from the arguments.
+fn take_option(args: &mut Vec<String>, option: &str) -> Result<Option<String>, Error> {
+    let Some(idx) = args.iter().position(|arg| arg == option) else {
+        return Ok(None);
+    };
+    if idx + 1 >= args.len() {
+        return Err(Error::argument(format!("{} needs a value", option)));
+    }
+
+    let value = args.remove(idx + 1);
+    args.remove(idx);
+    Ok(Some(value))
+}
+
+fn list() {
+    for puzzle in puzzles() {
+        println!("day {}", puzzle.day);
+    }
+}
+
+/// Returns `Ok(false)` when `--check` found answers that no longer match.
+fn run(mut args: Vec<String>) -> Result<bool, Error> {
+    let check = take_flag(&mut args, "--check");
+    let bench_runs = match take_option(&mut args, "--bench")? {
+        Some(runs) => Some(
+            runs.parse::<u32>()
+                .ok()
+                .filter(|runs| *runs > 0)
+                .ok_or_else(|| Error::argument(format!("invalid number of runs `{}`", runs)))?,
+        ),
+        None => None,
+    };
+    let threads = match take_option(&mut args, "--jobs")? {
+        Some(jobs) => jobs
+            .parse::<usize>()
+            .ok()
+            .filter(|jobs| *jobs > 0)
+            .ok_or_else(|| Error::argument(format!("invalid number of jobs `{}`", jobs)))?,
+        None => 1,
+    };
+
+    let mut args = Args::parse(args)?;
+    if args.help {
+        println!("{}\n\n{}", USAGE, cli::OPTIONS_HELP);
+        return Ok(true);
+    }
+    if args.stream {
+        return Err(Error::argument(
+            "--stream is only supported by the day binaries",
+        ));
+    }
+
+    let puzzles = puzzles();
+    let loaded = load_inputs(&mut args, &puzzles)?;
+
+    let mut checker = match check {
+        true => Some(Checker::load(ANSWERS_PATH)?),
+        false => None,
+    };
+
+    // Each run parses a day's input once, every part is then solved from it as its own job
+    let runs = bench_runs.unwrap_or(1);
+    let readable: Vec<_> = loaded
+        .iter()
+        .filter_map(|(puzzle, _, input)| Some((*puzzle, input.as_ref().ok()?)))
+        .collect();
+    let parse_jobs: Vec<_> = readable
+        .iter()
+        .map(|(puzzle, input)| {
+            move || {
+                (0..runs)
+                    .map(|_| puzzle.parse(input))
+                    .collect::<Result<Vec<_>, Error>>()
+            }
+        })
+        .collect();
+    let parses = pool::run(parse_jobs, threads);
+
+    let part_jobs: Vec<_> = readable
+        .iter()
+        .zip(&parses)
+        .filter_map(|((puzzle, _), parsed)| Some((*puzzle, parsed.as_ref().ok()?.as_ref().ok()?)))
+        .flat_map(|(puzzle, parsed)| {
+            args.parts.iter().map(move |part| {
+                move || {
+                    parsed
+                        .iter()
+                        .map(|run| puzzle.solve_part(run, *part))
+                        .collect::<Vec<_>>()
+                }
+            })
+        })
+        .collect();
+    let mut part_results = pool::run(part_jobs, threads).into_iter();
+    let mut parses = parses.into_iter();
+
+    let mut failed = false;
+    let mut timings = vec![];
+    let mut solutions = vec![];
+
+    for (puzzle, input_name, input) in &loaded {
+        let day = puzzle.day;
+        if let Err(error) = input {
+            eprintln!("error: day {}: {}", day, error);
+            failed = true;
+            continue;
+        }
+        let parsed = match parses.next().expect("one parse job per readable input") {
+            Ok(Ok(parsed)) => parsed,
+            Ok(Err(error)) => {
+                eprintln!("error: day {}: {}", day, error);
+                failed = true;
+                continue;
+            }
+            Err(panic) => {
+                eprintln!("error: day {}: panicked: {}", day, panic);
+                failed = true;
+                continue;
+            }
+        };
+
+        let mut day_runs: Vec<Solution> = parsed
+            .iter()
+            .map(|run| Solution {
+                day,
+                parse_elapsed: run.elapsed,
+                parts: vec![],
+            })
+            .collect();
+        let mut panicked = false;
+        for part in &args.parts {
+            match part_results.next().expect("one result per part job") {
+                Ok(part_runs) => {
+                    for (solution, result) in day_runs.iter_mut().zip(part_runs) {
+                        solution.parts.push(result);
+                    }
+                }
+                Err(panic) => {
+                    eprintln!("error: day {} part {}: panicked: {}", day, part, panic);
+                    panicked = true;
+                }
+            }
+        }
+        if panicked {
+            failed = true;
+            continue;
+        }
+
+        if bench_runs.is_some() {
+            let mut samples = Samples::new(day);
+            for run in &day_runs {
+                samples.add(run);
+            }
+            timings.push(samples);
+        }
+
+        let solution = day_runs.swap_remove(0);
+        if let Some(checker) = checker.as_mut() {
+            checker.report(input_name, &solution);
+            continue;
+        }
+
+        if args.format != Format::Text {
+            solutions.push(solution);
+            continue;
+        }
+        if !args.quiet {
+            println!("Day {}", day);
+        }
+        args.report(&solution);
+    }
+
+    if let Some(document) = report::render(args.format, &solutions) {
+        println!("{}", document.trim_end());
+    }
+
+    if !timings.is_empty() {
+        println!();
+        bench::print_table(&timings);
+    }
+
+    Ok(!failed && checker.is_none_or(|checker| checker.passed()))
+}
+
+/// A puzzle with the name of its input and the input, or why it couldn't be read.
+type Loaded<'a> = (&'a Puzzle, String, Result<String, Error>);
+
+/// Reads the input of every day named by the positional `<days> [input]` arguments, days
+/// without a solver are skipped unless an input was given for them. A day whose input can't
+/// be read keeps its error so the other days still run.
+fn load_inputs<'a>(args: &mut Args, puzzles: &'a [Puzzle]) -> Result<Vec<Loaded<'a>>, Error> {
+    let mut positional = std::mem::take(&mut args.positional).into_iter();
+    let days = parse_days(
+        &positional
+            .next()
+            .ok_or_else(|| Error::argument("no days given"))?,
+    )?;
+    if let Some(path) = positional.next() {
+        args.set_input(InputArg::from_arg(path))?;
+    }
+    if let Some(arg) = positional.next() {
+        return Err(Error::argument(format!("unexpected argument `{}`", arg)));
+    }
+
+    let explicit_input = matches!(args.input, InputArg::Path(_) | InputArg::Stdin);
+    if explicit_input && days.len() != 1 {
+        return Err(Error::argument(
+            "an input path can only be given for a single day",
+        ));
+    }
+
+    let mut loaded = vec![];
+    for day in days {
+        let puzzle = match puzzles.iter().find(|p| p.day == day) {
+            Some(puzzle) => puzzle,
+            None if explicit_input => {
+                return Err(Error::argument(format!("day {} has no solver", day)))
+            }
+            None => continue,
+        };
+
+        let input_name = args.source(day).to_string();
+        loaded.push((puzzle, input_name, args.load_input(day)));
+    }
+    Ok(loaded)
+}
+
+/// Returns `Ok(false)` when any input has problems.
+fn validate(args: Vec<String>) -> Result<bool, Error> {
+    let mut args = Args::parse(args)?;
+    if args.help {
+        println!("{}\n\n{}", USAGE, cli::OPTIONS_HELP);
+        return Ok(true);
+    }
+
+    let puzzles = puzzles();
+    let mut valid = true;
+    for (puzzle, input_name, input) in load_inputs(&mut args, &puzzles)? {
+        let input = match input {
+            Ok(input) => input,
+            Err(error) => {
+                println!("day {}: {}", puzzle.day, error);
+                valid = false;
+                continue;
+            }
+        };
+        let errors = puzzle.validate(&input);
+        match errors.len() {
+            0 => println!("day {}: {} ok", puzzle.day, input_name),
+            1 => println!("day {}: {} has 1 problem", puzzle.day, input_name),
+            n => println!("day {}: {} has {} problems", puzzle.day, input_name, n),
+        }
+        for error in &errors {
+            println!("    {}", error);
+        }
+        valid &= errors.is_empty();
+    }
+    Ok(valid)
+}
+
+fn parse_days(spec: &str) -> Result<Vec<u8>, Error> {
+    if spec == "all" {
+        return Ok(puzzles().iter().map(|p| p.day).collect());
+    }
+
+    let mut days = vec![];
+    for item in spec.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_day(from)?, parse_day(to)?);
+                if from > to {
+                    return Err(Error::argument(format!(
+                        "invalid day range `{}`, the first day comes after the last",
+                        item
+                    )));
+                }
+                days.extend(from..=to);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+
+    Ok(days)
+}
+
+fn parse_day(s: &str) -> Result<u8, Error> {
+    s.parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| Error::argument(format!("invalid day `{}`", s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{load_inputs, parse_days, puzzles};
+    use common::{generate, Args, Part};
+
+    #[test]
+    fn can_parse_day_specs() {
+        assert_eq!(vec![10], parse_days("10").unwrap());
+        assert_eq!(vec![1, 2, 3, 4], parse_days("1-4").unwrap());
+        assert_eq!(vec![1, 3, 10], parse_days("1,3,10").unwrap());
+        assert_eq!(vec![1, 2, 10], parse_days("1-2,10").unwrap());
+        assert!(parse_days("11-4").is_err());
+    }
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for puzzle in puzzles() {
+            let input = generate::input(puzzle.day, 8, 1).unwrap();
+
+            assert!(
+                puzzle.solve(&input, &Part::ALL).is_ok(),
+                "day {}",
+                puzzle.day
+            );
+        }
+    }
+
+    #[test]
+    fn generated_inputs_are_valid() {
+        for puzzle in puzzles() {
+            let input = generate::input(puzzle.day, 8, 1).unwrap();
+
+            assert_eq!(0, puzzle.validate(&input).len(), "day {}", puzzle.day);
+        }
+    }
+
+    #[test]
+    fn unreadable_inputs_are_kept_per_day() {
+        let puzzles = puzzles();
+        let mut args = Args::parse(vec!["1".into(), "no/such/input.txt".into()]).unwrap();
+        let loaded = load_inputs(&mut args, &puzzles).unwrap();
+
+        assert_eq!(1, loaded.len());
+        assert!(loaded[0].2.is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("ten").is_err());
+    }
+}
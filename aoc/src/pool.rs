@@ -0,0 +1,84 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Runs the jobs on up to `threads` threads and returns their results in the order the jobs
+/// were given. A job that panics gives `Err` with the panic message instead of taking the
+/// others down with it.
+pub fn run<T, F>(jobs: Vec<F>, threads: usize) -> Vec<Result<T, String>>
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+{
+    let count = jobs.len();
+    let jobs: Vec<Mutex<Option<F>>> = jobs.into_iter().map(|job| Mutex::new(Some(job))).collect();
+    let results: Vec<Mutex<Option<Result<T, String>>>> =
+        (0..count).map(|_| Mutex::new(None)).collect();
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                if idx >= count {
+                    break;
+                }
+
+                let job = jobs[idx]
+                    .lock()
+                    .unwrap()
+                    .take()
+                    .expect("each job runs once");
+                let result = panic::catch_unwind(AssertUnwindSafe(job)).map_err(panic_message);
+                *results[idx].lock().unwrap() = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.into_inner().unwrap().expect("every job has run"))
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pool::run;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn keeps_job_order_and_isolates_panics() {
+        let jobs: Vec<_> = (0..8u64)
+            .map(|n| {
+                move || {
+                    // Later jobs finish first
+                    thread::sleep(Duration::from_millis(8 - n));
+                    if n == 3 {
+                        panic!("job {} failed", n);
+                    }
+                    n * 10
+                }
+            })
+            .collect();
+
+        let results = run(jobs, 4);
+
+        assert_eq!(8, results.len());
+        assert_eq!(Ok(20), results[2]);
+        assert_eq!(Err("job 3 failed".to_string()), results[3]);
+        assert_eq!(Ok(70), results[7]);
+    }
+}
@@ -0,0 +1,232 @@
+use common::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TEMPLATE: &str = r#"[package]
+name = "day{day}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = { path = "../common" }
+"#;
+
+const MAIN_TEMPLATE: &str = r#"fn main() {
+    common::run::<day{day}::Day{day}>();
+}
+"#;
+
+const LIB_TEMPLATE: &str = r#"use common::{Answer, Error, Solver};
+
+pub struct Day{day};
+
+impl Solver for Day{day} {
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        common::parse_lines(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+}
+
+fn part_one(_input: &[String]) -> usize {
+    0
+}
+
+fn part_two(_input: &[String]) -> usize {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Day{day};
+
+    // TODO: Paste the puzzle's example into inputs/examples/d{day}.txt and fill in its answers
+    #[test]
+    fn example_answers() {
+        common::testing::assert_example::<Day{day}>(0, 0);
+    }
+}
+"#;
+
+/// Creates `day<day>` from the templates and registers it with the workspace and runner.
+/// `root` is the workspace root.
+pub fn scaffold(root: &Path, day: u8) -> Result<(), Error> {
+    let crate_dir = root.join(format!("day{}", day));
+    if crate_dir.exists() {
+        return Err(Error::argument(format!(
+            "{} already exists",
+            crate_dir.display()
+        )));
+    }
+
+    // Every edit is worked out before anything is written, so a manifest that cannot be
+    // changed leaves the tree as it was
+    let render = |template: &str| template.replace("{day}", &day.to_string());
+    let mut files = vec![
+        (crate_dir.join("Cargo.toml"), render(CARGO_TEMPLATE)),
+        (crate_dir.join("src/main.rs"), render(MAIN_TEMPLATE)),
+        (crate_dir.join("src/lib.rs"), render(LIB_TEMPLATE)),
+    ];
+    for input in [
+        format!("inputs/d{}-p1.txt", day),
+        format!("inputs/examples/d{}.txt", day),
+    ] {
+        let input = root.join(input);
+        if !input.exists() {
+            files.push((input, String::new()));
+        }
+    }
+
+    files.push(edit(&root.join("Cargo.toml"), |text| {
+        register(text, day, "    \"day", &format!("    \"day{}\",", day))
+    })?);
+    files.push(edit(&root.join("aoc/Cargo.toml"), |text| {
+        register(
+            text,
+            day,
+            "day",
+            &format!("day{} = {{ path = \"../day{}\" }}", day, day),
+        )
+    })?);
+    files.push(edit(&root.join("aoc/src/main.rs"), |text| {
+        register(
+            text,
+            day,
+            "        Puzzle::of::<day",
+            &format!("        Puzzle::of::<day{}::Day{}>(),", day, day),
+        )
+    })?);
+
+    for (path, contents) in &files {
+        if let Err(error) = write(path, contents) {
+            let _ = fs::remove_dir_all(&crate_dir);
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Inserts `entry` into the run of lines starting with `prefix` followed by a day number,
+/// keeping them ordered by day.
+fn register(text: &str, day: u8, prefix: &str, entry: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let rest = line.strip_prefix(prefix)?;
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok().map(|n| (idx, n))
+        })
+        .collect();
+
+    let Some(&(first, _)) = days.first() else {
+        return Err(format!(
+            "no existing `{}` entries to add day {} to",
+            prefix.trim(),
+            day
+        ));
+    };
+    let position = match days.iter().rev().find(|(_, n)| *n < day) {
+        Some((idx, _)) => idx + 1,
+        None => first,
+    };
+    lines.insert(position, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    let name = path.display().to_string();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| Error::write(&name, error))?;
+    }
+    fs::write(path, contents).map_err(|error| Error::write(&name, error))
+}
+
+/// The file's new contents, to be written once every edit is known to work.
+fn edit(
+    path: &Path,
+    change: impl Fn(&str) -> Result<String, String>,
+) -> Result<(PathBuf, String), Error> {
+    let name = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|error| Error::from_io(&name, error))?;
+    let changed =
+        change(&text).map_err(|reason| Error::argument(format!("{}: {}", name, reason)))?;
+    Ok((path.to_path_buf(), changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::{register, scaffold};
+    use std::fs;
+
+    #[test]
+    fn registers_days_in_order() {
+        let members = "members = [\n    \"day1\",\n    \"day10\",\n    \"common\",\n]\n";
+
+        assert_eq!(
+            "members = [\n    \"day1\",\n    \"day5\",\n    \"day10\",\n    \"common\",\n]\n",
+            register(members, 5, "    \"day", "    \"day5\",").unwrap()
+        );
+        assert_eq!(
+            "members = [\n    \"day1\",\n    \"day10\",\n    \"day12\",\n    \"common\",\n]\n",
+            register(members, 12, "    \"day", "    \"day12\",").unwrap()
+        );
+        assert!(register("[workspace]\n", 5, "    \"day", "    \"day5\",").is_err());
+    }
+
+    #[test]
+    fn scaffolds_a_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"day1\",\n    \"common\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "[dependencies]\nday1 = { path = \"../day1\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/main.rs"),
+            "    vec![\n        Puzzle::of::<day1::Day1>(),\n    ]\n",
+        )
+        .unwrap();
+
+        scaffold(&root, 7).unwrap();
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+
+        assert!(read("day7/src/lib.rs").contains("impl Solver for Day7 {"));
+        assert!(read("day7/src/main.rs").contains("common::run::<day7::Day7>()"));
+        assert!(read("Cargo.toml").contains("    \"day1\",\n    \"day7\",\n"));
+        assert!(read("aoc/Cargo.toml").contains("day7 = { path = \"../day7\" }"));
+        assert!(read("aoc/src/main.rs").contains("Puzzle::of::<day7::Day7>(),"));
+        assert_eq!("", read("inputs/d7-p1.txt"));
+        assert_eq!("", read("inputs/examples/d7.txt"));
+        assert!(scaffold(&root, 7).is_err());
+
+        // The runner has no `Puzzle::of` list to add to, so nothing is written
+        fs::write(root.join("aoc/src/main.rs"), "fn main() {}\n").unwrap();
+        assert!(scaffold(&root, 8).is_err());
+        assert!(!root.join("day8").exists());
+        assert!(!root.join("inputs/d8-p1.txt").exists());
+        assert!(!read("Cargo.toml").contains("day8"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
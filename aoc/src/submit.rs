@@ -0,0 +1,152 @@
+use common::{Answer, AnswerKey, AnswerStore, Error};
+use common::{Client, Verdict};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Posts an answer unless the store already knows how it will go, and records the verdict.
+/// Returns whether the answer is right.
+pub fn submit(
+    client: &Client,
+    store_path: &str,
+    key: AnswerKey,
+    answer: Answer,
+) -> Result<bool, Error> {
+    let mut store = match Path::new(store_path).exists() {
+        true => AnswerStore::load(store_path)?,
+        false => AnswerStore::default(),
+    };
+    let (day, part) = (key.day, key.part);
+
+    if let Some(known) = store.get(&key) {
+        if *known != answer {
+            return Err(Error::argument(format!(
+                "day {} part {} is already solved with `{}`, not submitting `{}`",
+                day, part, known, answer
+            )));
+        }
+        println!(
+            "day {} part {} `{}`: already recorded as the right answer",
+            day, part, answer
+        );
+        return Ok(true);
+    }
+    if store.is_known_wrong(&key, &answer) {
+        return Err(Error::argument(format!(
+            "`{}` is already known to be wrong for day {} part {}",
+            answer, day, part
+        )));
+    }
+
+    if let Some(left) = store.wait_remaining(SystemTime::now()) {
+        return Err(Error::argument(format!(
+            "answered too recently, wait {}m {}s before submitting again",
+            left.as_secs() / 60,
+            left.as_secs() % 60
+        )));
+    }
+
+    let (verdict, wait) = client.submit(day, part, &answer)?;
+    println!("day {} part {} `{}`: {}", day, part, answer, verdict);
+
+    match verdict {
+        Verdict::Correct => {
+            store.insert(key, answer);
+        }
+        verdict if verdict.is_wrong() => store.mark_wrong(key, answer),
+        Verdict::Wait(_) => (),
+        _ => return Ok(false),
+    };
+    if let Some(wait) = wait {
+        store.wait(SystemTime::now(), wait);
+    }
+    store.save(store_path)?;
+
+    Ok(verdict == Verdict::Correct)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::submit::submit;
+    use common::testing::serve;
+    use common::{Answer, AnswerKey, AnswerStore, Client, Part};
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn known_answers_are_not_resubmitted() {
+        let path = std::env::temp_dir().join(format!("aoc-submit-{}.toml", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(
+            path,
+            "[day1.\"inputs/d1-p1.txt\"]\npart1 = 24000\npart2_wrong = [45000]\n",
+        )
+        .unwrap();
+        // Nothing listens here, so any request that is made fails
+        let client = Client::new("http://127.0.0.1:9", Some("abc123".to_string()));
+        let key = |part| AnswerKey::new(1, "inputs/d1-p1.txt", part);
+
+        assert!(submit(&client, path, key(Part::One), Answer::Number(24000)).unwrap());
+        assert!(submit(&client, path, key(Part::One), Answer::Number(1))
+            .unwrap_err()
+            .to_string()
+            .contains("already solved"));
+        assert!(submit(&client, path, key(Part::Two), Answer::Number(45000))
+            .unwrap_err()
+            .to_string()
+            .contains("already known to be wrong"));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn waits_are_recorded_and_respected() {
+        let path = std::env::temp_dir().join(format!("aoc-wait-{}.toml", std::process::id()));
+        let path = path.to_str().unwrap();
+        let (base_url, server) = serve(vec![(
+            200,
+            "<article><p>You gave an answer too recently. You have 2m 5s left to wait.</p></article>",
+        )]);
+        let client = Client::new(base_url, Some("abc123".to_string()));
+        let key = AnswerKey::new(1, "inputs/d1-p1.txt", Part::One);
+
+        assert!(!submit(&client, path, key.clone(), Answer::Number(1)).unwrap());
+        assert_eq!(1, server.join().unwrap().len());
+
+        // The server is gone, so a second submission would fail to connect
+        let error = submit(&client, path, key, Answer::Number(2)).unwrap_err();
+        assert!(
+            error.to_string().contains("wait 2m"),
+            "{}",
+            error.to_string()
+        );
+
+        let store = AnswerStore::load(path).unwrap();
+        let left = store.wait_remaining(SystemTime::now()).unwrap();
+        assert!(left > Duration::from_secs(115) && left <= Duration::from_secs(126));
+        assert_eq!(None, store.wait_remaining(SystemTime::now() + left));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn wrong_answers_record_their_wait() {
+        let path = std::env::temp_dir().join(format!("aoc-lockout-{}.toml", std::process::id()));
+        let path = path.to_str().unwrap();
+        let (base_url, server) = serve(vec![(
+            200,
+            "<article><p>That's not the right answer. Please wait one minute before trying \
+            again.</p></article>",
+        )]);
+        let client = Client::new(base_url, Some("abc123".to_string()));
+        let key = AnswerKey::new(1, "inputs/d1-p1.txt", Part::One);
+
+        assert!(!submit(&client, path, key.clone(), Answer::Number(1)).unwrap());
+        server.join().unwrap();
+
+        let store = AnswerStore::load(path).unwrap();
+        assert!(store.is_known_wrong(&key, &Answer::Number(1)));
+        assert!(store.wait_remaining(SystemTime::now()).unwrap() > Duration::from_secs(55));
+
+        fs::remove_file(path).unwrap();
+    }
+}
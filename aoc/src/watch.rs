@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors often save in several writes, so changes are gathered for a moment before acting.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Polls modification times of every file under the watched paths, std has no change
+/// notifications. `target` directories are skipped.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    seen: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let seen = snapshot(&paths);
+        Watcher { paths, seen }
+    }
+
+    /// Files created, modified or removed since the last call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let now = snapshot(&self.paths);
+        let mut changed: Vec<PathBuf> = now
+            .iter()
+            .filter(|(path, modified)| self.seen.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.seen
+                .keys()
+                .filter(|path| !now.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+
+        self.seen = now;
+        changed
+    }
+
+    /// Blocks until something changes.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let mut changed = self.changed();
+            if !changed.is_empty() {
+                thread::sleep(SETTLE_TIME);
+                changed.extend(self.changed());
+                changed.sort();
+                changed.dedup();
+                return changed;
+            }
+        }
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        let Ok(meta) = fs::metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            if path.file_name().is_some_and(|name| name == "target") {
+                continue;
+            }
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        } else if let Ok(modified) = meta.modified() {
+            files.insert(path, modified);
+        }
+    }
+    files
+}
+
+/// What `cargo test` reported, summed over every test binary.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: usize,
+    /// Each failing test with the first line of its panic message.
+    pub failures: Vec<(String, String)>,
+}
+
+impl TestSummary {
+    /// `None` when there are no results, because the tests did not build.
+    pub fn parse(stdout: &str) -> Option<Self> {
+        let mut summary = TestSummary::default();
+        let mut found = false;
+        let lines: Vec<&str> = stdout.lines().collect();
+
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(result) = line.strip_prefix("test result: ") {
+                found = true;
+                for count in result.split(['.', ';']) {
+                    let mut words = count.split_whitespace();
+                    match (words.next().map(str::parse::<usize>), words.next()) {
+                        (Some(Ok(n)), Some("passed")) => summary.passed += n,
+                        (Some(Ok(n)), Some("failed")) => summary.failed += n,
+                        _ => (),
+                    }
+                }
+            } else if let Some(name) = line
+                .strip_prefix("---- ")
+                .and_then(|rest| rest.strip_suffix(" stdout ----"))
+            {
+                // The message follows the panic location, up to the next test's output
+                let message = lines[idx + 1..]
+                    .iter()
+                    .take_while(|line| !line.starts_with("---- ") && **line != "failures:")
+                    .skip_while(|line| !line.contains("panicked at"))
+                    .nth(1)
+                    .unwrap_or(&"")
+                    .to_string();
+                summary.failures.push((name.to_string(), message));
+            }
+        }
+
+        found.then_some(summary)
+    }
+}
+
+/// Runs the day's tests and then its binary on each input, printing one compact block.
+pub fn check(day: u8, inputs: &[(String, Vec<String>)]) {
+    let package = format!("day{}", day);
+    let tests = match cargo(&["test", "-q", "-p", &package]) {
+        Ok(output) => output,
+        Err(error) => {
+            println!("{:<9}could not run cargo: {}", "build", error);
+            return;
+        }
+    };
+
+    let Some(summary) = TestSummary::parse(&String::from_utf8_lossy(&tests.stdout)) else {
+        println!("{:<9}FAILED", "build");
+        print_build_errors(&String::from_utf8_lossy(&tests.stderr));
+        return;
+    };
+    match summary.failed {
+        0 => println!("{:<9}ok, {} passed", "tests", summary.passed),
+        failed => {
+            println!(
+                "{:<9}FAILED, {} passed, {} failed",
+                "tests", summary.passed, failed
+            );
+            for (name, message) in &summary.failures {
+                println!("{:<9}{}: {}", "", name, message);
+            }
+        }
+    }
+
+    for (label, input_args) in inputs {
+        let mut args = vec!["run", "-q", "--bin", &package, "--", "--quiet"];
+        args.extend(input_args.iter().map(String::as_str));
+
+        let (text, output) = match cargo(&args) {
+            Ok(output) if output.status.success() => ("", output.stdout),
+            Ok(output) => ("FAILED ", output.stderr),
+            Err(error) => ("FAILED ", error.to_string().into_bytes()),
+        };
+        let output = String::from_utf8_lossy(&output);
+        let lines: Vec<&str> = output.trim_end().lines().collect();
+
+        // Two answers fit on one line, anything longer holds a picture
+        match lines.as_slice() {
+            [] | [_] | [_, _] => println!("{:<9}{}{}", label, text, lines.join("  ")),
+            [first, rest @ ..] => {
+                println!("{:<9}{}{}", label, text, first);
+                for line in rest {
+                    println!("{:<9}{}", "", line);
+                }
+            }
+        }
+    }
+}
+
+fn cargo(args: &[&str]) -> std::io::Result<Output> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    Command::new(cargo).args(args).output()
+}
+
+/// Only the `error` lines and where they are, the full output is a `cargo build` away.
+fn print_build_errors(stderr: &str) {
+    let mut lines = stderr.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("error") && !line.starts_with("error: could not compile") {
+            println!("{:<9}{}", "", line);
+            if let Some(location) = lines.next().filter(|next| next.contains("-->")) {
+                println!("{:<9}{}", "", location.trim());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::watch::{TestSummary, Watcher};
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn notices_changed_added_and_removed_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        let (lib, new, built) = (
+            dir.join("src/lib.rs"),
+            dir.join("src/new.rs"),
+            dir.join("target/out"),
+        );
+        fs::write(&lib, "").unwrap();
+
+        let mut watcher = Watcher::new(vec![dir.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // Set the time outright, a rewrite can land within the filesystem's timestamp
+        // resolution
+        let file = fs::File::options().write(true).open(&lib).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+        fs::write(&new, "").unwrap();
+        fs::write(&built, "").unwrap();
+        assert_eq!(vec![lib.clone(), new.clone()], watcher.changed());
+
+        fs::remove_file(&new).unwrap();
+        assert_eq!(vec![new], watcher.changed());
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn summarizes_test_output() {
+        let output = "
+running 3 tests
+.F.
+failures:
+
+---- tests::example_answers stdout ----
+
+thread 'tests::example_answers' (123) panicked at day3/src/lib.rs:5:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+
+failures:
+    tests::example_answers
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        assert_eq!(
+            Some(TestSummary {
+                passed: 2,
+                failed: 1,
+                failures: vec![(
+                    "tests::example_answers".to_string(),
+                    "assertion `left == right` failed".to_string()
+                )],
+            }),
+            TestSummary::parse(output)
+        );
+        assert_eq!(None, TestSummary::parse(""));
+    }
+}
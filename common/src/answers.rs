@@ -0,0 +1,459 @@
+use crate::{numbered_lines, Answer, Error, InputSource, Line, Part};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+const HEADER: &str =
+    "# Known good answers, checked with `cargo run --bin aoc -- run all --check`\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AnswerKey {
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+}
+
+impl AnswerKey {
+    pub fn new(day: u8, input: impl Into<String>, part: Part) -> Self {
+        AnswerKey {
+            day,
+            input: input.into(),
+            part,
+        }
+    }
+}
+
+/// Known good answers, stored as a small subset of TOML with one table per day and input:
+///
+/// ```toml
+/// [day1."inputs/d1-p1.txt"]
+/// part1 = 68787
+/// part2 = 198041
+/// ```
+///
+/// Multi-line answers (such as day 10's CRT) use `"""` strings. Answers a submission
+/// showed to be wrong are kept in `part1_wrong = [123, 456]` lists. When the site asks to
+/// wait before answering again, the deadline is kept as `wait_until = <unix seconds>` above
+/// the tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    answers: BTreeMap<AnswerKey, Answer>,
+    wrong: BTreeMap<AnswerKey, Vec<Answer>>,
+    wait_until: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+impl AnswerStore {
+    pub fn load(path: &str) -> Result<Self, Error> {
+        AnswerStore::parse(&InputSource::path(path).read_to_string()?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut store = AnswerStore::default();
+        let mut table: Option<(u8, String)> = None;
+        let mut lines = numbered_lines(text);
+
+        while let Some(line) = lines.next() {
+            let content = strip_comment(line.text).trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            if content.starts_with('[') {
+                table = Some(parse_table_header(&line, content)?);
+                continue;
+            }
+
+            let (key, value) = content
+                .split_once('=')
+                .ok_or_else(|| line.error("expected `key = value`"))?;
+            if key.trim() == "wait_until" && table.is_none() {
+                let seconds = value.trim().parse().map_err(|error| line.error(error))?;
+                store.wait_until = Some(seconds);
+                continue;
+            }
+            let (part, wrong) = match key.trim() {
+                "part1" => (Part::One, false),
+                "part2" => (Part::Two, false),
+                "part1_wrong" => (Part::One, true),
+                "part2_wrong" => (Part::Two, true),
+                other => return Err(line.error(format!("unknown key `{}`", other))),
+            };
+            let (day, input) = table
+                .clone()
+                .ok_or_else(|| line.error("answer given before any [day.\"input\"] table"))?;
+            let key = AnswerKey::new(day, input, part);
+
+            if wrong {
+                store.wrong.insert(key, parse_list(&line, value.trim())?);
+            } else {
+                let answer = parse_value(&line, value.trim(), &mut lines)?;
+                store.insert(key, answer);
+            }
+        }
+
+        Ok(store)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let text = format!("{}\n{}", HEADER, self.to_toml());
+        fs::write(path, text).map_err(|error| Error::write(path, error))
+    }
+
+    pub fn get(&self, key: &AnswerKey) -> Option<&Answer> {
+        self.answers.get(key)
+    }
+
+    pub fn insert(&mut self, key: AnswerKey, answer: Answer) -> Option<Answer> {
+        self.answers.insert(key, answer)
+    }
+
+    pub fn mark_wrong(&mut self, key: AnswerKey, answer: Answer) {
+        let wrong = self.wrong.entry(key).or_default();
+        if !wrong.contains(&answer) {
+            wrong.push(answer);
+        }
+    }
+
+    pub fn is_known_wrong(&self, key: &AnswerKey, answer: &Answer) -> bool {
+        self.wrong
+            .get(key)
+            .is_some_and(|wrong| wrong.contains(answer))
+    }
+
+    /// How long is left to wait before answering again, `None` once the deadline has passed.
+    pub fn wait_remaining(&self, now: SystemTime) -> Option<Duration> {
+        let until = UNIX_EPOCH + Duration::from_secs(self.wait_until?);
+        until
+            .duration_since(now)
+            .ok()
+            .filter(|left| !left.is_zero())
+    }
+
+    pub fn wait(&mut self, now: SystemTime, wait: Duration) {
+        let until = now.duration_since(UNIX_EPOCH).unwrap_or_default() + wait;
+        // Round up so the deadline is never early
+        self.wait_until = Some(until.as_secs() + u64::from(until.subsec_nanos() > 0));
+    }
+
+    pub fn check(&self, key: &AnswerKey, actual: &Answer) -> Check {
+        match self.get(key) {
+            None => Check::Unknown,
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        let tables: BTreeSet<(u8, &str)> = self
+            .answers
+            .keys()
+            .chain(self.wrong.keys())
+            .map(|key| (key.day, key.input.as_str()))
+            .collect();
+
+        let mut out = String::new();
+        if let Some(until) = self.wait_until {
+            let _ = writeln!(out, "wait_until = {}\n", until);
+        }
+        for (idx, (day, input)) in tables.into_iter().enumerate() {
+            if idx > 0 {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "[day{}.{}]", day, quote(input));
+
+            for part in Part::ALL {
+                let key = AnswerKey::new(day, input, part);
+                if let Some(answer) = self.answers.get(&key) {
+                    let value = match answer {
+                        Answer::Text(s) if s.contains('\n') => format!("\"\"\"\n{}\n\"\"\"", s),
+                        answer => to_value(answer),
+                    };
+                    let _ = writeln!(out, "part{} = {}", part, value);
+                }
+                if let Some(wrong) = self.wrong.get(&key) {
+                    let values: Vec<String> = wrong.iter().map(to_value).collect();
+                    let _ = writeln!(out, "part{}_wrong = [{}]", part, values.join(", "));
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// Line by line comparison of two answers, `-` lines are expected and `+` lines actual.
+pub fn diff(expected: &Answer, actual: &Answer) -> Vec<String> {
+    let expected = expected.to_string();
+    let actual = actual.to_string();
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    let mut out = vec![];
+    for idx in 0..expected.len().max(actual.len()) {
+        let (left, right) = (expected.get(idx), actual.get(idx));
+        if left == right {
+            continue;
+        }
+        if let Some(left) = left {
+            out.push(format!("- {}", left));
+        }
+        if let Some(right) = right {
+            out.push(format!("+ {}", right));
+        }
+    }
+    out
+}
+
+fn strip_comment(text: &str) -> &str {
+    let mut in_string = false;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &text[..idx],
+            _ => (),
+        }
+    }
+    text
+}
+
+fn parse_table_header(line: &Line, content: &str) -> Result<(u8, String), Error> {
+    let inner = content
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| line.error("expected `[dayN.\"input\"]`"))?;
+    let (day, input) = inner
+        .split_once('.')
+        .ok_or_else(|| line.error("expected `[dayN.\"input\"]`"))?;
+
+    let day = day
+        .trim()
+        .strip_prefix("day")
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| line.error(format!("invalid day `{}`", day.trim())))?;
+    let input = unquote(input.trim()).ok_or_else(|| line.error("expected a quoted input path"))?;
+
+    Ok((day, input))
+}
+
+fn parse_value<'a>(
+    line: &Line,
+    value: &str,
+    lines: &mut impl Iterator<Item = Line<'a>>,
+) -> Result<Answer, Error> {
+    if let Some(first) = value.strip_prefix("\"\"\"") {
+        // Multi-line string, TOML drops a newline directly after the opening quotes and
+        // answers never end in a newline, so one before the closing quotes is dropped too
+        let mut text = String::new();
+        let mut current = first.to_string();
+        loop {
+            if let Some(end) = current.find("\"\"\"") {
+                text.push_str(&current[..end]);
+                let text = text.strip_suffix('\n').unwrap_or(&text);
+                return Ok(Answer::Text(unescape(text)));
+            }
+            if !(text.is_empty() && current.is_empty()) {
+                text.push_str(&current);
+                text.push('\n');
+            }
+            current = match lines.next() {
+                Some(next) => next.text.to_string(),
+                None => return Err(line.error("unterminated multi-line string")),
+            };
+        }
+    }
+
+    parse_scalar(line, value)
+}
+
+/// A single line `[1, "two"]` list, multi-line strings are not allowed in here.
+fn parse_list(line: &Line, value: &str) -> Result<Vec<Answer>, Error> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| line.error("expected a `[...]` list"))?;
+
+    let mut items = vec![];
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, c) in inner.char_indices().chain([(inner.len(), ',')]) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                let item = inner[start..idx].trim();
+                if !item.is_empty() {
+                    items.push(parse_scalar(line, item)?);
+                }
+                start = idx + 1;
+            }
+            _ => (),
+        }
+    }
+    Ok(items)
+}
+
+fn parse_scalar(line: &Line, value: &str) -> Result<Answer, Error> {
+    if value.starts_with('"') {
+        return unquote(value)
+            .map(Answer::Text)
+            .ok_or_else(|| line.error("unterminated string"));
+    }
+
+    value
+        .replace('_', "")
+        .parse()
+        .map(Answer::Number)
+        .map_err(|_| line.error(format!("invalid value `{}`", value)))
+}
+
+fn to_value(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => quote(s),
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    Some(unescape(inner))
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::{diff, AnswerKey, AnswerStore, Check};
+    use crate::{Answer, Part};
+
+    const STORE: &str = r#"
+# Known answers
+[day1."inputs/d1-p1.txt"]
+part1 = 68_787
+part2 = 198041 # top three
+part2_wrong = [198040, "1,2"]
+
+[day10."inputs/examples/d10.txt"]
+part1 = 13140
+part2 = """
+##..
+###.
+"""
+"#;
+
+    #[test]
+    fn can_parse_answers() {
+        let store = AnswerStore::parse(STORE).unwrap();
+
+        assert_eq!(
+            Some(&Answer::Number(68787)),
+            store.get(&AnswerKey::new(1, "inputs/d1-p1.txt", Part::One))
+        );
+        assert_eq!(
+            Some(&Answer::Text("##..\n###.".to_string())),
+            store.get(&AnswerKey::new(10, "inputs/examples/d10.txt", Part::Two))
+        );
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let store = AnswerStore::parse(STORE).unwrap();
+
+        assert_eq!(store, AnswerStore::parse(&store.to_toml()).unwrap());
+    }
+
+    #[test]
+    fn checks_against_recorded_answers() {
+        let store = AnswerStore::parse(STORE).unwrap();
+        let key = AnswerKey::new(1, "inputs/d1-p1.txt", Part::Two);
+
+        assert_eq!(Check::Pass, store.check(&key, &Answer::Number(198041)));
+        assert_eq!(
+            Check::Fail {
+                expected: Answer::Number(198041)
+            },
+            store.check(&key, &Answer::Number(1))
+        );
+        assert_eq!(
+            Check::Unknown,
+            store.check(&AnswerKey::new(2, "x", Part::One), &Answer::Number(1))
+        );
+    }
+
+    #[test]
+    fn remembers_wrong_answers() {
+        let mut store = AnswerStore::parse(STORE).unwrap();
+        let key = AnswerKey::new(1, "inputs/d1-p1.txt", Part::Two);
+        store.mark_wrong(key.clone(), Answer::Number(5));
+        store.mark_wrong(key.clone(), Answer::Number(5));
+
+        assert!(store.is_known_wrong(&key, &Answer::Number(198040)));
+        assert!(store.is_known_wrong(&key, &Answer::Text("1,2".to_string())));
+        assert!(!store.is_known_wrong(&key, &Answer::Number(198041)));
+        assert!(store
+            .to_toml()
+            .contains("part2 = 198041\npart2_wrong = [198040, \"1,2\", 5]\n"));
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let error = AnswerStore::parse("[day1.\"a\"]\npart3 = 1").unwrap_err();
+
+        assert_eq!(
+            "line 2: unknown key `part3` (`part3 = 1`)",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn diffs_only_changed_lines() {
+        let expected = Answer::Text("##..\n....\n#..#".to_string());
+        let actual = Answer::Text("##..\n.#..\n#..#".to_string());
+
+        assert_eq!(vec!["- ....", "+ .#.."], diff(&expected, &actual));
+    }
+}
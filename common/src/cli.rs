@@ -0,0 +1,235 @@
+use crate::report::{self, Format};
+use crate::{print_answer, Error, InputSource, Part, Solution};
+
+pub const OPTIONS_HELP: &str = "options:
+    --part <1|2|both>   which part(s) to solve, defaults to both
+    --input <path>      puzzle input file, `-` reads from stdin
+    --example           use the bundled example input instead
+    --stream            read the input a line at a time instead of loading it,
+                        for very large inputs
+    --quiet             only print the answers, one per line
+    --format <format>   text, json or csv, the last two include the answer kind
+                        and how long each part took
+    --json              same as --format json
+    --help              show this help";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputArg {
+    Default,
+    Path(String),
+    Stdin,
+    Example,
+}
+
+impl InputArg {
+    pub fn from_arg(value: String) -> Self {
+        match value.as_str() {
+            "-" => InputArg::Stdin,
+            _ => InputArg::Path(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub parts: Vec<Part>,
+    pub input: InputArg,
+    pub stream: bool,
+    pub quiet: bool,
+    pub format: Format,
+    pub help: bool,
+    pub positional: Vec<String>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            parts: Part::ALL.to_vec(),
+            input: InputArg::Default,
+            stream: false,
+            quiet: false,
+            format: Format::Text,
+            help: false,
+            positional: vec![],
+        }
+    }
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, Error> {
+        let mut parsed = Args::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => parsed.parts = parse_parts(&flag_value(&arg, args.next())?)?,
+                "--input" => {
+                    let value = flag_value(&arg, args.next())?;
+                    parsed.set_input(InputArg::from_arg(value))?;
+                }
+                "--example" => parsed.set_input(InputArg::Example)?,
+                "--stream" => parsed.stream = true,
+                "--quiet" => parsed.quiet = true,
+                "--format" => parsed.format = Format::parse(&flag_value(&arg, args.next())?)?,
+                "--json" => parsed.format = Format::Json,
+                "--help" | "-h" => parsed.help = true,
+                "-" => parsed.positional.push(arg),
+                _ if arg.starts_with('-') => {
+                    return Err(Error::argument(format!("unknown option `{}`", arg)))
+                }
+                _ => parsed.positional.push(arg),
+            }
+        }
+
+        if parsed.quiet && parsed.format != Format::Text {
+            return Err(Error::argument("--quiet only applies to the text format"));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parses the process arguments for a day binary, where the input path may also be
+    /// given positionally as `dayN <path>`.
+    pub fn from_env() -> Result<Self, Error> {
+        Args::from_args(std::env::args().skip(1))
+    }
+
+    /// Parses options followed by at most one input.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, Error> {
+        let mut args = Args::parse(args)?;
+
+        if let Some(path) = args.positional.pop() {
+            if !args.positional.is_empty() {
+                return Err(Error::argument(format!(
+                    "unexpected argument `{}`",
+                    args.positional[0]
+                )));
+            }
+            args.set_input(InputArg::from_arg(path))?;
+        }
+
+        Ok(args)
+    }
+
+    pub fn set_input(&mut self, input: InputArg) -> Result<(), Error> {
+        if self.input != InputArg::Default {
+            return Err(Error::argument("only one input can be given"));
+        }
+        self.input = input;
+        Ok(())
+    }
+
+    pub fn source(&self, day: u8) -> InputSource<'static> {
+        match &self.input {
+            InputArg::Default => InputSource::path(default_input_path(day)),
+            InputArg::Path(path) => InputSource::path(path.clone()),
+            InputArg::Example => InputSource::path(example_input_path(day)),
+            InputArg::Stdin => InputSource::Stdin,
+        }
+    }
+
+    pub fn load_input(&self, day: u8) -> Result<String, Error> {
+        self.fetch_missing(day)?;
+        self.source(day).read_to_string()
+    }
+
+    /// Downloads the default input if it isn't in `inputs/` yet and a session is set up,
+    /// otherwise reading it reports the missing file as usual.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_missing(&self, day: u8) -> Result<(), Error> {
+        let cached = std::fs::metadata(default_input_path(day)).is_ok_and(|meta| meta.len() > 0);
+        if self.input != InputArg::Default || cached {
+            return Ok(());
+        }
+
+        let client = crate::Client::from_env()?;
+        if client.session.is_some() {
+            client.input(day)?;
+        }
+        Ok(())
+    }
+
+    /// Inputs are only fetched with the `fetch` feature.
+    #[cfg(not(feature = "fetch"))]
+    pub fn fetch_missing(&self, _day: u8) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn report(&self, solution: &Solution) {
+        if let Some(document) = report::render(self.format, std::slice::from_ref(solution)) {
+            println!("{}", document.trim_end());
+        } else if self.quiet {
+            for result in &solution.parts {
+                println!("{}", result.answer);
+            }
+        } else {
+            for result in &solution.parts {
+                print_answer(result.part, &result.answer);
+            }
+        }
+    }
+}
+
+pub fn default_input_path(day: u8) -> String {
+    format!("inputs/d{}-p1.txt", day)
+}
+
+pub fn example_input_path(day: u8) -> String {
+    format!("inputs/examples/d{}.txt", day)
+}
+
+pub fn parse_parts(value: &str) -> Result<Vec<Part>, Error> {
+    match value {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        "both" => Ok(Part::ALL.to_vec()),
+        _ => Err(Error::argument(format!(
+            "invalid part `{}`, expected 1, 2 or both",
+            value
+        ))),
+    }
+}
+
+fn flag_value(flag: &str, value: Option<String>) -> Result<String, Error> {
+    value.ok_or_else(|| Error::argument(format!("{} needs a value", flag)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Args, InputArg};
+    use crate::report::Format;
+    use crate::Part;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn defaults_to_both_parts_and_default_input() {
+        let parsed = Args::parse(args(&[])).unwrap();
+
+        assert_eq!(vec![Part::One, Part::Two], parsed.parts);
+        assert_eq!(InputArg::Default, parsed.input);
+    }
+
+    #[test]
+    fn can_parse_flags() {
+        let parsed = Args::parse(args(&["--part", "2", "--input", "-", "--json"])).unwrap();
+
+        assert_eq!(vec![Part::Two], parsed.parts);
+        assert_eq!(InputArg::Stdin, parsed.input);
+        assert_eq!(Format::Json, parsed.format);
+        assert_eq!(
+            Format::Csv,
+            Args::parse(args(&["--format", "csv"])).unwrap().format
+        );
+    }
+
+    #[test]
+    fn rejects_conflicting_inputs() {
+        assert!(Args::parse(args(&["--example", "--input", "a.txt"])).is_err());
+        assert!(Args::parse(args(&["--part", "3"])).is_err());
+        assert!(Args::parse(args(&["--verbose"])).is_err());
+        assert!(Args::parse(args(&["--quiet", "--format", "csv"])).is_err());
+    }
+}
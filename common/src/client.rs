@@ -0,0 +1,396 @@
+use crate::cli::default_input_path;
+use crate::{numbered_lines, Answer, Error, Part};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+pub const CONFIG_PATH: &str = "aoc.toml";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = "github.com/hexnaught/aoc-2022";
+
+/// Assumed when a page asks for a wait without saying how long, the longest of the usual
+/// lockouts after a wrong answer.
+pub const DEFAULT_WAIT: Duration = Duration::from_secs(5 * 60);
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the last answer, nothing was checked.
+    Wait(Duration),
+    /// The part was already solved, nothing was checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the response page, `None` if it says something unexpected.
+    pub fn parse(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Some(Verdict::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Some(Verdict::Wait(wait(page).unwrap_or(DEFAULT_WAIT)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "right answer"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+            Verdict::TooHigh => write!(f, "wrong answer, too high"),
+            Verdict::TooLow => write!(f, "wrong answer, too low"),
+            Verdict::Wait(wait) => write!(
+                f,
+                "answered too recently, wait {}m {}s",
+                wait.as_secs() / 60,
+                wait.as_secs() % 60
+            ),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// How long the response page asks to wait before answering again, `None` if it doesn't.
+/// Wrong answers come with a wait as well as answers given too recently.
+pub fn wait(page: &str) -> Option<Duration> {
+    let asked = ["left to wait", "before trying again"]
+        .iter()
+        .any(|phrase| page.contains(phrase));
+    if !asked {
+        return None;
+    }
+    Some(
+        parse_wait(page)
+            .or_else(|| parse_lockout(page))
+            .unwrap_or(DEFAULT_WAIT),
+    )
+}
+
+/// `You have 1m 23s left to wait.`
+fn parse_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in page[start..end].split_whitespace() {
+        seconds += match (amount.strip_suffix('m'), amount.strip_suffix('s')) {
+            (Some(minutes), _) => minutes.parse::<u64>().ok()? * 60,
+            (_, Some(secs)) => secs.parse::<u64>().ok()?,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// `Please wait one minute before trying again.`, or `5 minutes`.
+fn parse_lockout(page: &str) -> Option<Duration> {
+    let start = page.find("wait ")? + "wait ".len();
+    let end = start + page[start..].find(" before trying again")?;
+
+    let minutes = match page[start..end].split_whitespace().collect::<Vec<_>>()[..] {
+        ["one", "minute"] => 1,
+        [amount, "minutes"] => amount.parse::<u64>().ok()?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// Talks to Advent of Code, or anything serving the same paths under `base_url`.
+///
+/// Settings come from `AOC_SESSION` and `AOC_BASE_URL`, falling back to `session` and
+/// `base_url` keys in `aoc.toml`:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "http://localhost:8080/2022"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Client {
+    pub base_url: String,
+    pub session: Option<String>,
+    /// Fetched inputs are kept under `inputs/` in here.
+    pub cache_dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+            cache_dir: PathBuf::new(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, Error> {
+        let mut client = if Path::new(CONFIG_PATH).exists() {
+            Client::parse_config(&crate::read_file(CONFIG_PATH)?)?
+        } else {
+            Client::new(DEFAULT_BASE_URL, None)
+        };
+
+        if let Ok(session) = env::var(SESSION_VAR) {
+            client.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            client.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        Ok(client)
+    }
+
+    /// Reads the `key = "value"` lines of the config file, `#` starts a comment.
+    pub fn parse_config(text: &str) -> Result<Self, Error> {
+        let mut client = Client::new(DEFAULT_BASE_URL, None);
+
+        for line in numbered_lines(text) {
+            let content = line.text.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let (key, value) = content
+                .split_once('=')
+                .ok_or_else(|| line.error("expected `key = \"value\"`"))?;
+            let value = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .ok_or_else(|| line.error("expected a quoted value"))?;
+
+            match key.trim() {
+                "session" => client.session = Some(value.to_string()),
+                "base_url" => client.base_url = value.trim_end_matches('/').to_string(),
+                other => return Err(line.error(format!("unknown key `{}`", other))),
+            }
+        }
+
+        Ok(client)
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(default_input_path(day))
+    }
+
+    /// The day's puzzle input, downloaded once and read from `inputs/` after that.
+    pub fn input(&self, day: u8) -> Result<String, Error> {
+        let path = self.input_path(day);
+        let name = path.display().to_string();
+
+        // An empty file is what `aoc scaffold` leaves behind, so it does not count
+        if let Ok(cached) = fs::read_to_string(&path) {
+            if !cached.is_empty() {
+                return Ok(cached);
+            }
+        }
+
+        let input = self.get(&format!("/day/{}/input", day))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| Error::write(&name, error))?;
+        }
+        fs::write(&path, &input).map_err(|error| Error::write(&name, error))?;
+        Ok(input)
+    }
+
+    /// Posts an answer, returning the verdict and how long to wait before the next one.
+    pub fn submit(
+        &self,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<(Verdict, Option<Duration>), Error> {
+        let url = format!("{}/day/{}/answer", self.base_url, day);
+        let session = self.session()?;
+
+        let page = ureq::post(&url)
+            .header("Cookie", &format!("session={}", session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|error| Error::fetch(&url, error))?;
+
+        let verdict =
+            Verdict::parse(&page).ok_or_else(|| Error::fetch(&url, "unrecognised response"))?;
+        Ok((verdict, wait(&page)))
+    }
+
+    fn session(&self) -> Result<&str, Error> {
+        self.session.as_deref().ok_or_else(|| {
+            Error::argument(format!(
+                "no session token, set {} or add `session` to {}",
+                SESSION_VAR, CONFIG_PATH
+            ))
+        })
+    }
+
+    fn get(&self, path: &str) -> Result<String, Error> {
+        let url = format!("{}{}", self.base_url, path);
+        let session = self.session()?;
+
+        ureq::get(&url)
+            .header("Cookie", &format!("session={}", session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|error| Error::fetch(&url, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::{wait, Client, Verdict, DEFAULT_WAIT};
+    use crate::testing::serve;
+    use crate::{Answer, Part};
+    use std::time::Duration;
+
+    fn temp_client(base_url: &str, name: &str) -> Client {
+        let mut client = Client::new(base_url, Some("abc123".to_string()));
+        client.cache_dir =
+            std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        client
+    }
+
+    #[test]
+    fn fetches_once_then_reads_the_cache() {
+        let (base_url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let client = temp_client(&base_url, "cache");
+
+        assert_eq!("1000\n2000\n", client.input(1).unwrap());
+        assert_eq!("1000\n2000\n", client.input(1).unwrap());
+
+        let requests = server.join().unwrap();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("session=abc123"));
+        std::fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn failed_fetches_are_not_cached() {
+        let (base_url, server) = serve(vec![(404, "Not Found")]);
+        let client = temp_client(&base_url, "missing");
+
+        let error = client.input(25).unwrap_err();
+
+        assert!(error.to_string().starts_with("could not fetch"));
+        assert!(!client.input_path(25).exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too low. Please wait \
+            one minute before trying again.</p></article>",
+        )]);
+        let client = Client::new(base_url, Some("abc123".to_string()));
+
+        let response = client.submit(1, Part::Two, &Answer::Number(42)).unwrap();
+
+        assert_eq!((Verdict::TooLow, Some(Duration::from_secs(60))), response);
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/1/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn can_parse_verdicts() {
+        let wait = "You gave an answer too recently; you have to wait after submitting an \
+            answer before trying again.  You have 1m 23s left to wait.";
+
+        assert_eq!(
+            Some(Verdict::Correct),
+            Verdict::parse("That's the right answer! You are one gold star closer.")
+        );
+        assert_eq!(
+            Some(Verdict::TooHigh),
+            Verdict::parse("That's not the right answer; your answer is too high.")
+        );
+        assert_eq!(
+            Some(Verdict::Wrong),
+            Verdict::parse("That's not the right answer.")
+        );
+        assert_eq!(
+            Some(Verdict::Wait(Duration::from_secs(83))),
+            Verdict::parse(wait)
+        );
+        assert_eq!(
+            Some(Verdict::AlreadySolved),
+            Verdict::parse("You don't seem to be solving the right level.")
+        );
+        assert_eq!(
+            None,
+            Verdict::parse("<html>Puzzle inputs differ by user.</html>")
+        );
+        assert_eq!(
+            Some(Verdict::Wait(DEFAULT_WAIT)),
+            Verdict::parse("You gave an answer too recently.")
+        );
+    }
+
+    #[test]
+    fn can_parse_waits() {
+        let wrong = "That's not the right answer.";
+
+        assert_eq!(None, wait(wrong));
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            wait(&format!(
+                "{} Please wait one minute before trying again.",
+                wrong
+            ))
+        );
+        assert_eq!(
+            Some(Duration::from_secs(300)),
+            wait(&format!(
+                "{} Please wait 5 minutes before trying again.",
+                wrong
+            ))
+        );
+        assert_eq!(
+            Some(DEFAULT_WAIT),
+            wait(&format!(
+                "{} Please wait a while before trying again.",
+                wrong
+            ))
+        );
+    }
+
+    #[test]
+    fn can_parse_config() {
+        let client = Client::parse_config(
+            "# local server\nsession = \"abc123\"\nbase_url = \"http://localhost:8080/2022/\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Client::new("http://localhost:8080/2022", Some("abc123".to_string())),
+            client
+        );
+        assert!(Client::parse_config("token = \"x\"").is_err());
+    }
+}
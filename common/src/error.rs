@@ -0,0 +1,128 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    FileNotFound {
+        path: String,
+    },
+    Unreadable {
+        path: String,
+        source: io::Error,
+    },
+    Unwritable {
+        path: String,
+        source: io::Error,
+    },
+    Argument(String),
+    Fetch {
+        url: String,
+        reason: String,
+    },
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        text: String,
+        reason: String,
+    },
+}
+
+impl Error {
+    pub fn argument(message: impl Into<String>) -> Self {
+        Error::Argument(message.into())
+    }
+
+    /// `line` is 1-based, matching what an editor shows for the input file.
+    pub fn parse(line: usize, text: &str, reason: impl fmt::Display) -> Self {
+        Error::Parse {
+            line,
+            column: None,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn parse_at(line: usize, column: usize, text: &str, reason: impl fmt::Display) -> Self {
+        Error::Parse {
+            line,
+            column: Some(column),
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn from_io(path: &str, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::NotFound => Error::FileNotFound {
+                path: path.to_string(),
+            },
+            _ => Error::Unreadable {
+                path: path.to_string(),
+                source,
+            },
+        }
+    }
+
+    pub fn write(path: &str, source: io::Error) -> Self {
+        Error::Unwritable {
+            path: path.to_string(),
+            source,
+        }
+    }
+
+    pub fn fetch(url: &str, reason: impl fmt::Display) -> Self {
+        Error::Fetch {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Exit code for the binaries, bad usage is kept apart from bad input.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Argument(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::FileNotFound { path } => write!(f, "input file `{}` not found", path),
+            Error::Unreadable { path, source } => {
+                write!(f, "could not read input file `{}`: {}", path, source)
+            }
+            Error::Unwritable { path, source } => {
+                write!(f, "could not write `{}`: {}", path, source)
+            }
+            Error::Argument(message) => write!(f, "{}", message),
+            Error::Fetch { url, reason } => write!(f, "could not fetch `{}`: {}", url, reason),
+            Error::Parse {
+                line,
+                column: None,
+                text,
+                reason,
+            } => write!(f, "line {}: {} (`{}`)", line, reason, text),
+            Error::Parse {
+                line,
+                column: Some(column),
+                text,
+                reason,
+            } => write!(
+                f,
+                "line {}, column {}: {} (`{}`)",
+                line, column, reason, text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Unreadable { source, .. } | Error::Unwritable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,338 @@
+//! Seeded random inputs in each day's format, for stress testing and benchmarking. The same
+//! seed and size always give the same input.
+
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// SplitMix64, small and good enough for test data, not for anything that needs security.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % span) as i64
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..=len as i64 - 1) as usize
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.index(idx + 1));
+        }
+    }
+}
+
+/// Generates an input for `day` from `seed`, `size` is the number of elves, rounds, groups,
+/// pairs, instructions or monkeys (2 to `MAX_MONKEYS`). `None` for days without a generator,
+/// or a number of monkeys out of that range.
+pub fn input(day: u8, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    match day {
+        1 => Some(calories(rng, size)),
+        2 => Some(strategy_guide(rng, size)),
+        3 => Some(rucksacks(rng, size)),
+        4 => Some(section_pairs(rng, size)),
+        10 => Some(cpu_program(rng, size)),
+        11 if (2..=MAX_MONKEYS).contains(&size) => Some(monkeys(rng, size)),
+        _ => None,
+    }
+}
+
+/// Day 1, blank line separated groups of 1 to 15 calorie counts.
+pub fn calories(rng: &mut Rng, elves: usize) -> String {
+    let groups: Vec<String> = (0..elves)
+        .map(|_| {
+            let items: Vec<String> = (0..rng.range(1..=15))
+                .map(|_| rng.range(1000..=9999).to_string())
+                .collect();
+            items.join("\n")
+        })
+        .collect();
+
+    groups.join("\n\n") + "\n"
+}
+
+/// Day 2, `A Y` rounds.
+pub fn strategy_guide(rng: &mut Rng, rounds: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..rounds {
+        let _ = writeln!(
+            out,
+            "{} {}",
+            rng.pick(&['A', 'B', 'C']),
+            rng.pick(&['X', 'Y', 'Z'])
+        );
+    }
+    out
+}
+
+/// Day 3, groups of three rucksacks. Each rucksack's halves share exactly one item type and
+/// each group shares exactly one badge, as the puzzle promises.
+pub fn rucksacks(rng: &mut Rng, groups: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..groups {
+        let mut types: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut types);
+        let badge = types[0];
+
+        // Each elf draws from its own 17 types, so the badge is the only one they share
+        for pool in types[1..].chunks(17) {
+            let (shared, rest) = (pool[0], &pool[1..]);
+            let (left_types, right_types) = rest.split_at(rest.len() / 2);
+            let badge_left = rng.range(0..=1) == 0;
+
+            let half = rng.range(4..=16) as usize;
+            let mut left = compartment(rng, half, shared, left_types, badge_left.then_some(badge));
+            let right = compartment(
+                rng,
+                half,
+                shared,
+                right_types,
+                (!badge_left).then_some(badge),
+            );
+
+            left.extend(right);
+            let _ = writeln!(out, "{}", left.into_iter().collect::<String>());
+        }
+    }
+    out
+}
+
+fn compartment(
+    rng: &mut Rng,
+    len: usize,
+    shared: char,
+    types: &[char],
+    badge: Option<char>,
+) -> Vec<char> {
+    let mut items = vec![shared];
+    items.extend(badge);
+    while items.len() < len {
+        items.push(*rng.pick(types));
+    }
+    rng.shuffle(&mut items);
+    items
+}
+
+/// Day 4, `2-4,6-8` section ranges between 1 and 99.
+pub fn section_pairs(rng: &mut Rng, pairs: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..pairs {
+        let mut range = || {
+            let start = rng.range(1..=99);
+            (start, rng.range(start..=99))
+        };
+        let ((a, b), (c, d)) = (range(), range());
+        let _ = writeln!(out, "{}-{},{}-{}", a, b, c, d);
+    }
+    out
+}
+
+/// Day 10, `noop` and `addx` instructions that keep the sprite on the 40 pixel wide screen.
+pub fn cpu_program(rng: &mut Rng, instructions: usize) -> String {
+    let mut out = String::new();
+    let mut x = 1;
+    for _ in 0..instructions {
+        if rng.range(0..=2) == 0 {
+            out.push_str("noop\n");
+        } else {
+            let target = rng.range(0..=39);
+            let _ = writeln!(out, "addx {}", target - x);
+            x = target;
+        }
+    }
+    out
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    operator: char,
+    operand: Option<u64>,
+    divisor: u64,
+    targets: (usize, usize),
+}
+
+/// Part two squares worry levels below the product of every test, so the primes are kept
+/// small enough for that square to fit in a `u64`.
+const DIVISORS: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+pub const MAX_MONKEYS: usize = DIVISORS.len();
+
+/// Sets with a squaring monkey drawn before settling for one where every monkey adds.
+const SQUARING_DRAWS: usize = 100;
+
+/// Day 11, monkeys with distinct prime tests, one of them squaring the worry level. Sets
+/// that would overflow `u64` during part one's 20 rounds are drawn again, and if none fit,
+/// as with two monkeys passing every item through the squaring one, every monkey adds.
+pub fn monkeys(rng: &mut Rng, count: usize) -> String {
+    assert!(
+        (2..=MAX_MONKEYS).contains(&count),
+        "between 2 and {} monkeys can be generated",
+        MAX_MONKEYS
+    );
+
+    let mut draws = 0;
+    let monkeys = loop {
+        draws += 1;
+        let adding = draws > SQUARING_DRAWS;
+        let mut divisors = DIVISORS.to_vec();
+        rng.shuffle(&mut divisors);
+        let squaring = rng.index(count);
+
+        let monkeys: Vec<Monkey> = (0..count)
+            .map(|idx| {
+                let mut other = || loop {
+                    let target = rng.index(count);
+                    if target != idx {
+                        break target;
+                    }
+                };
+                let targets = (other(), other());
+                let (operator, operand) = match (adding, idx == squaring) {
+                    (true, _) => ('+', Some(rng.range(1..=19) as u64)),
+                    (false, true) => ('*', None),
+                    (false, false) => (*rng.pick(&['+', '*']), Some(rng.range(1..=19) as u64)),
+                };
+
+                Monkey {
+                    items: (0..rng.range(1..=8))
+                        .map(|_| rng.range(50..=99) as u64)
+                        .collect(),
+                    operator,
+                    operand,
+                    divisor: divisors[idx],
+                    targets,
+                }
+            })
+            .collect();
+
+        // Adding can't overflow, items gain at most 19 per throw
+        if adding || part_one_fits(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    let mut out = String::new();
+    for (idx, monkey) in monkeys.iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        let items: Vec<String> = monkey.items.iter().map(u64::to_string).collect();
+        let operand = monkey.operand.map_or("old".to_string(), |n| n.to_string());
+
+        let _ = writeln!(out, "Monkey {}:", idx);
+        let _ = writeln!(out, "  Starting items: {}", items.join(", "));
+        let _ = writeln!(
+            out,
+            "  Operation: new = old {} {}",
+            monkey.operator, operand
+        );
+        let _ = writeln!(out, "  Test: divisible by {}", monkey.divisor);
+        let _ = writeln!(out, "    If true: throw to monkey {}", monkey.targets.0);
+        let _ = writeln!(out, "    If false: throw to monkey {}", monkey.targets.1);
+    }
+    out
+}
+
+/// Plays part one's rounds, where worry only shrinks by dividing by 3.
+fn part_one_fits(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+
+    for _ in 0..20 {
+        for (idx, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[idx]) {
+                let operand = monkey.operand.unwrap_or(item);
+                let new = match monkey.operator {
+                    '+' => item.checked_add(operand),
+                    _ => item.checked_mul(operand),
+                };
+                let Some(new) = new.map(|n| n / 3) else {
+                    return false;
+                };
+
+                let target = match new % monkey.divisor {
+                    0 => monkey.targets.0,
+                    _ => monkey.targets.1,
+                };
+                items[target].push(new);
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::{input, rucksacks, Rng, MAX_MONKEYS};
+    use std::collections::HashSet;
+
+    #[test]
+    fn same_seed_gives_the_same_input() {
+        for day in [1, 2, 3, 4, 10, 11] {
+            assert_eq!(input(day, 5, 7), input(day, 5, 7));
+            assert_ne!(input(day, 5, 7), input(day, 5, 8));
+        }
+        assert_eq!(None, input(25, 5, 7));
+    }
+
+    #[test]
+    fn every_number_of_monkeys_can_be_generated() {
+        for count in 2..=MAX_MONKEYS {
+            let monkeys = input(11, count, 1).unwrap();
+            assert_eq!(count, monkeys.matches("Monkey").count());
+        }
+        assert_eq!(None, input(11, 1, 1));
+        assert_eq!(None, input(11, MAX_MONKEYS + 1, 1));
+    }
+
+    #[test]
+    fn sizes_control_the_input_length() {
+        assert_eq!(100, input(2, 100, 1).unwrap().lines().count());
+        assert_eq!(60, input(3, 20, 1).unwrap().lines().count());
+        assert_eq!(40, input(4, 40, 1).unwrap().lines().count());
+        assert_eq!(4, input(1, 4, 1).unwrap().split("\n\n").count());
+        assert_eq!(8 * 7 - 1, input(11, 8, 1).unwrap().lines().count());
+    }
+
+    #[test]
+    fn rucksacks_share_one_item_and_one_badge() {
+        let sacks = rucksacks(&mut Rng::new(3), 50);
+        let sacks: Vec<&str> = sacks.lines().collect();
+        let types = |s: &str| s.chars().collect::<HashSet<char>>();
+
+        for sack in &sacks {
+            let (left, right) = sack.split_at(sack.len() / 2);
+            assert_eq!(
+                1,
+                types(left).intersection(&types(right)).count(),
+                "{}",
+                sack
+            );
+        }
+        for group in sacks.chunks(3) {
+            let badges = &(&types(group[0]) & &types(group[1])) & &types(group[2]);
+            assert_eq!(1, badges.len());
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{numbered_lines, Error};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid stored row by row, positions are `(x, y)` with `(0, 0)` top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one row per line, `cell` turns each character into a value or rejects it.
+    pub fn parse(input: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self, Error> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        // Blank lines after the last row are only the end of the file
+        for line in numbered_lines(input.trim_end()) {
+            let text = line.text.trim_end();
+            for (idx, c) in text.chars().enumerate() {
+                let value = cell(c).ok_or_else(|| {
+                    Error::parse_at(line.number, idx + 1, text, format!("unexpected `{}`", c))
+                })?;
+                cells.push(value);
+            }
+
+            let len = text.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(
+                        line.error(format!("expected a row of {} cells, found {}", width, len))
+                    )
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.offset(x, y).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.offset(x, y).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Up, right, down and left of `(x, y)`, skipping those off the grid.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// All eight positions around `(x, y)` including diagonals, skipping those off the grid.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &SURROUNDING)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.offset(position.0, position.1).map(|_| position)
+        })
+    }
+
+    fn offset(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", x, y))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", x, y))
+    }
+}
+
+/// One line per row with no trailing newline, so a grid of `char` prints as it was parsed.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    const TREES: &str = "30373\n25512\n65332\n33549\n35390";
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(TREES, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn can_parse_and_index() {
+        let grid = digits();
+
+        assert_eq!((5, 5), (grid.width(), grid.height()));
+        assert_eq!(7, grid[(3, 0)]);
+        assert_eq!(Some(&9), grid.get(4, 3));
+        assert_eq!(None, grid.get(5, 0));
+        assert_eq!(TREES, grid.to_string());
+    }
+
+    #[test]
+    fn can_iterate_rows_and_columns() {
+        let grid = digits();
+
+        assert_eq!(&[2, 5, 5, 1, 2], grid.row(1));
+        assert_eq!(
+            vec![3, 5, 3, 5, 3],
+            grid.column(2).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(5, grid.columns().count());
+        assert_eq!(25, grid.positions().count());
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::new(3, 3, '.');
+
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours(0, 0).collect::<Vec<_>>()
+        );
+        assert_eq!(4, grid.neighbours(1, 1).count());
+        assert_eq!(3, grid.neighbours8(2, 2).count());
+        assert_eq!(8, grid.neighbours8(1, 1).count());
+    }
+
+    #[test]
+    fn can_draw_into_a_grid() {
+        let mut grid = Grid::new(3, 2, '.');
+        grid[(1, 0)] = '#';
+        grid[(2, 1)] = '#';
+
+        assert_eq!(".#.\n..#", grid.to_string());
+    }
+
+    #[test]
+    fn ignores_blank_lines_at_the_end() {
+        let grid = Grid::parse(&format!("{}\n\n\n", TREES), |c| c.to_digit(10)).unwrap();
+
+        assert_eq!((5, 5), (grid.width(), grid.height()));
+    }
+
+    #[test]
+    fn rejects_bad_cells_and_ragged_rows() {
+        let bad_cell = Grid::parse("123\n1x3", |c| c.to_digit(10)).unwrap_err();
+        let ragged = Grid::parse("123\n12", |c| c.to_digit(10)).unwrap_err();
+
+        assert_eq!(
+            "line 2, column 2: unexpected `x` (`1x3`)",
+            bad_cell.to_string()
+        );
+        assert_eq!(
+            "line 2: expected a row of 3 cells, found 2 (`12`)",
+            ragged.to_string()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{parse_lines, Error, Line};
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+
+/// Where a puzzle input comes from, so solvers never need to care whether it was a file,
+/// a pipe or a string baked into a test.
+pub enum InputSource<'a> {
+    Path(String),
+    Stdin,
+    Embedded(&'a str),
+    Reader(Box<dyn Read + 'a>),
+}
+
+impl<'a> InputSource<'a> {
+    pub fn path(path: impl Into<String>) -> Self {
+        InputSource::Path(path.into())
+    }
+
+    pub fn reader(reader: impl Read + 'a) -> Self {
+        InputSource::Reader(Box::new(reader))
+    }
+
+    pub fn read_to_string(self) -> Result<String, Error> {
+        let name = self.to_string();
+        let mut input = String::new();
+
+        match self {
+            InputSource::Path(path) => File::open(&path)
+                .and_then(|mut file| file.read_to_string(&mut input))
+                .map_err(|error| Error::from_io(&path, error))?,
+            InputSource::Stdin => io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| Error::from_io(&name, error))?,
+            InputSource::Embedded(text) => return Ok(text.to_string()),
+            InputSource::Reader(mut reader) => reader
+                .read_to_string(&mut input)
+                .map_err(|error| Error::from_io(&name, error))?,
+        };
+
+        Ok(input)
+    }
+
+    /// Reads the input lazily, one line at a time.
+    pub fn lines(self) -> Result<ReadLines<'a>, Error> {
+        let name = self.to_string();
+        let reader: Box<dyn BufRead + 'a> = match self {
+            InputSource::Path(path) => {
+                let file = File::open(&path).map_err(|error| Error::from_io(&path, error))?;
+                Box::new(BufReader::new(file))
+            }
+            InputSource::Stdin => Box::new(io::stdin().lock()),
+            InputSource::Embedded(text) => Box::new(text.as_bytes()),
+            InputSource::Reader(reader) => Box::new(BufReader::new(reader)),
+        };
+
+        Ok(ReadLines {
+            reader,
+            name,
+            number: 0,
+            done: false,
+        })
+    }
+
+    pub fn parse_lines<T>(self) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        parse_lines(&self.read_to_string()?)
+    }
+}
+
+/// A line owned by `ReadLines`, `line()` borrows it as a `Line` to parse it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadLine {
+    pub number: usize,
+    pub text: String,
+}
+
+impl ReadLine {
+    pub fn line(&self) -> Line<'_> {
+        Line {
+            number: self.number,
+            text: &self.text,
+        }
+    }
+}
+
+/// Lines pulled from a buffered reader as they are needed, so memory use does not grow
+/// with the input. Line endings (`\n` or `\r\n`) are removed.
+pub struct ReadLines<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    name: String,
+    number: usize,
+    done: bool,
+}
+
+impl<'a> ReadLines<'a> {
+    /// Parses each line as it is read, errors carry the line number.
+    pub fn parsed<T>(self) -> impl Iterator<Item = Result<T, Error>> + 'a
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        self.map(|line| line?.line().parse())
+    }
+}
+
+impl Iterator for ReadLines<'_> {
+    type Item = Result<ReadLine, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut text = String::new();
+        match self.reader.read_line(&mut text) {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => {
+                if text.ends_with('\n') {
+                    text.pop();
+                    if text.ends_with('\r') {
+                        text.pop();
+                    }
+                }
+                self.number += 1;
+                Some(Ok(ReadLine {
+                    number: self.number,
+                    text,
+                }))
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(Error::from_io(&self.name, error)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Embedded(_) => write!(f, "<embedded>"),
+            InputSource::Reader(_) => write!(f, "<reader>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::InputSource;
+    use crate::Error;
+    use std::io::Cursor;
+
+    #[test]
+    fn can_read_embedded_and_reader_sources() {
+        let embedded = InputSource::Embedded("1\n2\n3")
+            .parse_lines::<i32>()
+            .unwrap();
+        let reader = InputSource::reader(Cursor::new("1\n2\n3"))
+            .parse_lines::<i32>()
+            .unwrap();
+
+        assert_eq!(vec![1, 2, 3], embedded);
+        assert_eq!(embedded, reader);
+    }
+
+    #[test]
+    fn can_stream_lines() {
+        let lines: Vec<String> = InputSource::reader(Cursor::new("a\r\n\nb"))
+            .lines()
+            .unwrap()
+            .map(|line| line.unwrap().text)
+            .collect();
+        let parsed: Vec<Result<i32, Error>> = InputSource::Embedded("1\nx\n3")
+            .lines()
+            .unwrap()
+            .parsed()
+            .collect();
+
+        assert_eq!(vec!["a", "", "b"], lines);
+        assert_eq!(1, *parsed[0].as_ref().unwrap());
+        assert_eq!(
+            "line 2: invalid digit found in string (`x`)",
+            parsed[1].as_ref().unwrap_err().to_string()
+        );
+        assert_eq!(3, *parsed[2].as_ref().unwrap());
+    }
+
+    #[test]
+    fn missing_paths_are_reported() {
+        let result = InputSource::path("does/not/exist.txt").read_to_string();
+
+        assert!(matches!(result, Err(Error::FileNotFound { .. })));
+    }
+}
@@ -1,20 +1,107 @@
-use std::fmt::Error;
+use std::fmt::Display;
+use std::process;
 use std::str::FromStr;
-use std::env;
-
-pub fn read_file_lines<T: FromStr>(file_name: &str) -> Result<Vec<T>, <T as FromStr>::Err> {
-    std::fs::read_to_string(file_name)
-        .expect("file not found!")
-        .lines()
-        .map(|x| x.parse())
-        .collect()
+
+pub mod answers;
+pub mod cli;
+#[cfg(feature = "fetch")]
+pub mod client;
+mod error;
+pub mod generate;
+mod grid;
+mod input;
+pub mod parse;
+pub mod report;
+pub mod scan;
+mod solver;
+pub mod testing;
+
+pub use answers::{AnswerKey, AnswerStore};
+pub use cli::Args;
+#[cfg(feature = "fetch")]
+pub use client::{Client, Verdict};
+pub use error::Error;
+pub use grid::Grid;
+pub use input::{InputSource, ReadLine, ReadLines};
+pub use parse::{blocks, numbered_lines, Block, Line};
+pub use scan::{scan, List, ScanError};
+pub use solver::{
+    format_duration, print_answer, Answer, Parsed, Part, PartResult, Puzzle, Solution, Solver,
+};
+
+pub fn read_file_lines<T>(file_name: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    InputSource::path(file_name).lines()?.parsed().collect()
 }
 
-pub fn get_filepath_arg() -> Result<String, Error> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 as usize {
-        eprintln!("invalid number of arguments");
-        return Err(Error);
+pub fn read_file(file_name: &str) -> Result<String, Error> {
+    InputSource::path(file_name).read_to_string()
+}
+
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    numbered_lines(input).map(|line| line.parse()).collect()
+}
+
+pub fn run<S: Solver>() {
+    if let Err(error) = try_run::<S>() {
+        exit_with(&error);
+    }
+}
+
+fn try_run<S: Solver>() -> Result<(), Error> {
+    let args = Args::from_env()?;
+    if args.help {
+        println!(
+            "usage: day{} [options] [input]\n\n{}",
+            S::DAY,
+            cli::OPTIONS_HELP
+        );
+        return Ok(());
+    }
+
+    let puzzle = Puzzle::of::<S>();
+    args.fetch_missing(S::DAY)?;
+    let solution = match args.stream {
+        true => puzzle.solve_stream(args.source(S::DAY), &args.parts)?,
+        false => puzzle.solve(&args.load_input(S::DAY)?, &args.parts)?,
+    };
+    args.report(&solution);
+    Ok(())
+}
+
+pub fn exit_with(error: &Error) -> ! {
+    eprintln!("error: {}", error);
+    process::exit(error.exit_code());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_lines, Error};
+
+    #[test]
+    fn parse_lines_reports_the_failing_line() {
+        let result = parse_lines::<i32>("1\n2\nthree\n4");
+
+        match result {
+            Err(Error::Parse { line, text, .. }) => {
+                assert_eq!(3, line);
+                assert_eq!("three", text);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_file_reports_missing_files() {
+        let result = crate::read_file("does/not/exist.txt");
+
+        assert!(matches!(result, Err(Error::FileNotFound { .. })));
     }
-    Ok(args[1].clone())
 }
@@ -0,0 +1,151 @@
+use crate::scan::{scan, FromFields};
+use crate::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A single input line along with its 1-based line number, so parse failures can point
+/// back at the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl Line<'_> {
+    pub fn scan<T: FromFields>(&self, pattern: &str) -> Result<T, Error> {
+        scan(pattern, self.text).map_err(|error| error.at_line(self.number, self.text))
+    }
+
+    pub fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        self.text.parse().map_err(|error| self.error(error))
+    }
+
+    pub fn error(&self, reason: impl Display) -> Error {
+        Error::parse(self.number, self.text, reason)
+    }
+
+    /// Error for the character at 1-based `column`.
+    pub fn error_at(&self, column: usize, reason: impl Display) -> Error {
+        Error::parse_at(self.number, column, self.text, reason)
+    }
+}
+
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(idx, text)| Line {
+        number: idx + 1,
+        text,
+    })
+}
+
+/// A group of consecutive non-blank lines, with trailing whitespace (and any `\r`) removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// 1-based line number of the first line in the block.
+    pub start_line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    pub fn numbered(&self) -> impl Iterator<Item = Line<'a>> + '_ {
+        self.lines.iter().enumerate().map(|(idx, text)| Line {
+            number: self.start_line + idx,
+            text,
+        })
+    }
+
+    pub fn parse_lines<T>(&self) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        self.numbered().map(|line| line.parse()).collect()
+    }
+
+    /// Error for a block that ran out of lines, pointing just past its last line.
+    pub fn ended_early(&self, expected: &str) -> Error {
+        Error::parse(
+            self.start_line + self.lines.len(),
+            "",
+            format!("expected {}", expected),
+        )
+    }
+}
+
+pub struct Blocks<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block: Option<Block<'a>> = None;
+
+        for (idx, line) in self.lines.by_ref() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                match block {
+                    Some(_) => break,
+                    None => continue,
+                }
+            }
+
+            block
+                .get_or_insert_with(|| Block {
+                    start_line: idx + 1,
+                    lines: vec![],
+                })
+                .lines
+                .push(line);
+        }
+
+        block
+    }
+}
+
+/// Splits the input into groups of lines separated by one or more blank lines. A
+/// missing separator after the last group is fine.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks {
+        lines: input.lines().enumerate(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::blocks;
+
+    #[test]
+    fn splits_on_blank_lines() {
+        let groups: Vec<Vec<&str>> = blocks("1\n2\n\n3\n\n\n4\n5\n")
+            .map(|block| block.lines)
+            .collect();
+
+        assert_eq!(vec![vec!["1", "2"], vec!["3"], vec!["4", "5"]], groups);
+    }
+
+    #[test]
+    fn tolerates_crlf_whitespace_and_no_final_separator() {
+        let groups: Vec<Vec<&str>> = blocks("\r\n1 \r\n2\r\n  \r\n3\t")
+            .map(|block| block.lines)
+            .collect();
+
+        assert_eq!(vec![vec!["1", "2"], vec!["3"]], groups);
+    }
+
+    #[test]
+    fn parse_errors_use_input_line_numbers() {
+        let block = blocks("1\n\n2\nx").nth(1).unwrap();
+
+        assert_eq!(3, block.start_line);
+        assert!(block
+            .parse_lines::<i32>()
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 4"));
+    }
+}
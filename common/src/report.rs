@@ -0,0 +1,203 @@
+use crate::{Answer, Error, Part, Solution};
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+/// How answers are printed, `Text` is for people and the others for tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(Error::argument(format!(
+                "invalid format `{}`, expected text, json or csv",
+                value
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerKind {
+    Integer,
+    String,
+    /// A multi-line picture to be read by eye, such as day 10's CRT.
+    Image,
+}
+
+impl AnswerKind {
+    pub fn of(answer: &Answer) -> Self {
+        match answer {
+            Answer::Number(_) => AnswerKind::Integer,
+            Answer::Text(s) if s.contains('\n') => AnswerKind::Image,
+            Answer::Text(_) => AnswerKind::String,
+        }
+    }
+}
+
+impl fmt::Display for AnswerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerKind::Integer => write!(f, "integer"),
+            AnswerKind::String => write!(f, "string"),
+            AnswerKind::Image => write!(f, "image"),
+        }
+    }
+}
+
+/// One answered part, flattened out of a `Solution` for reporting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub kind: AnswerKind,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+impl Record {
+    pub fn from_solution(solution: &Solution) -> Vec<Record> {
+        solution
+            .parts
+            .iter()
+            .map(|result| Record {
+                day: solution.day,
+                part: result.part,
+                kind: AnswerKind::of(&result.answer),
+                answer: result.answer.clone(),
+                elapsed: result.elapsed,
+            })
+            .collect()
+    }
+}
+
+/// Renders the answers of every solution as one document, `None` for `Format::Text`.
+pub fn render(format: Format, solutions: &[Solution]) -> Option<String> {
+    let records: Vec<Record> = solutions.iter().flat_map(Record::from_solution).collect();
+
+    match format {
+        Format::Text => None,
+        Format::Json => Some(to_json(&records)),
+        Format::Csv => Some(to_csv(&records)),
+    }
+}
+
+/// A JSON array with one object per record, images are given as an array of rows.
+pub fn to_json(records: &[Record]) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let answer = match (&record.answer, record.kind) {
+                (Answer::Number(n), _) => n.to_string(),
+                (Answer::Text(s), AnswerKind::Image) => {
+                    let rows: Vec<String> = s.lines().map(json_string).collect();
+                    format!("[{}]", rows.join(","))
+                }
+                (Answer::Text(s), _) => json_string(s),
+            };
+            format!(
+                "{{\"day\":{},\"part\":{},\"kind\":\"{}\",\"answer\":{},\"elapsed_ns\":{}}}",
+                record.day,
+                record.part,
+                record.kind,
+                answer,
+                record.elapsed.as_nanos()
+            )
+        })
+        .collect();
+
+    format!("[{}]", objects.join(","))
+}
+
+/// CSV with a header row, fields are quoted when needed so images keep their newlines.
+pub fn to_csv(records: &[Record]) -> String {
+    let mut out = String::from("day,part,kind,answer,elapsed_ns\n");
+    for record in records {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            record.day,
+            record.part,
+            record.kind,
+            csv_field(&record.answer.to_string()),
+            record.elapsed.as_nanos()
+        );
+    }
+    out
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{render, Format};
+    use crate::{Answer, Part, PartResult, Solution};
+    use std::time::Duration;
+
+    fn solution() -> Solution {
+        let result = |part, answer, micros| PartResult {
+            part,
+            answer,
+            elapsed: Duration::from_micros(micros),
+        };
+        Solution {
+            day: 10,
+            parse_elapsed: Duration::ZERO,
+            parts: vec![
+                result(Part::One, Answer::Number(42), 3),
+                result(Part::Two, Answer::Text("#.\n.#".to_string()), 5),
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_json() {
+        assert_eq!(
+            concat!(
+                r#"[{"day":10,"part":1,"kind":"integer","answer":42,"elapsed_ns":3000},"#,
+                r##"{"day":10,"part":2,"kind":"image","answer":["#.",".#"],"elapsed_ns":5000}]"##
+            ),
+            render(Format::Json, &[solution()]).unwrap()
+        );
+    }
+
+    #[test]
+    fn renders_csv() {
+        assert_eq!(
+            "day,part,kind,answer,elapsed_ns\n10,1,integer,42,3000\n10,2,image,\"#.\n.#\",5000\n",
+            render(Format::Csv, &[solution()]).unwrap()
+        );
+        assert_eq!(None, render(Format::Text, &[solution()]));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Why a line did not match a `scan` pattern. `column` is 1-based and relative to the
+/// scanned text, `ScanError::at_line` turns it into a located `Error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanError {
+    pub column: usize,
+    pub reason: String,
+}
+
+impl ScanError {
+    pub fn new(column: usize, reason: impl Into<String>) -> Self {
+        ScanError {
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn at_line(self, line: usize, text: &str) -> Error {
+        Error::parse_at(line, self.column, text, self.reason)
+    }
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.reason)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field<'a> {
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl Field<'_> {
+    pub fn parse<T: FromField>(&self) -> Result<T, ScanError> {
+        T::from_field(*self)
+    }
+}
+
+pub trait FromField: Sized {
+    fn from_field(field: Field) -> Result<Self, ScanError>;
+}
+
+impl<T> FromField for T
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    fn from_field(field: Field) -> Result<Self, ScanError> {
+        field.text.parse().map_err(|error| {
+            ScanError::new(
+                field.column,
+                format!("could not parse `{}`: {}", field.text, error),
+            )
+        })
+    }
+}
+
+/// A comma separated field such as `79, 98`, whitespace around each item is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T: FromField> FromField for List<T> {
+    fn from_field(field: Field) -> Result<Self, ScanError> {
+        let mut items = vec![];
+        let mut offset = 0;
+
+        for item in field.text.split(',') {
+            let leading = item.len() - item.trim_start().len();
+            items.push(T::from_field(Field {
+                column: field.column + offset + leading,
+                text: item.trim(),
+            })?);
+            offset += item.len() + 1;
+        }
+
+        Ok(List(items))
+    }
+}
+
+pub trait FromFields: Sized {
+    fn from_fields(fields: &[Field]) -> Result<Self, ScanError>;
+}
+
+macro_rules! impl_from_fields {
+    ($count:literal $(, $ty:ident $idx:tt)*) => {
+        impl<$($ty: FromField),*> FromFields for ($($ty,)*) {
+            #[allow(unused_variables)]
+            fn from_fields(fields: &[Field]) -> Result<Self, ScanError> {
+                assert_eq!(
+                    $count,
+                    fields.len(),
+                    "scan pattern and requested fields differ in length"
+                );
+                Ok(($(fields[$idx].parse::<$ty>()?,)*))
+            }
+        }
+    };
+}
+
+impl_from_fields!(0);
+impl_from_fields!(1, A 0);
+impl_from_fields!(2, A 0, B 1);
+impl_from_fields!(3, A 0, B 1, C 2);
+impl_from_fields!(4, A 0, B 1, C 2, D 3);
+impl_from_fields!(5, A 0, B 1, C 2, D 3, E 4);
+impl_from_fields!(6, A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Matches `text` against a pattern where each `{}` is a field, e.g. `"{}-{},{}-{}"`, and
+/// parses the fields into a tuple.
+///
+/// A field runs up to the first occurrence of the literal text after it, or to the end of
+/// the line for a trailing field. Two fields must not be adjacent.
+pub fn scan<T: FromFields>(pattern: &str, text: &str) -> Result<T, ScanError> {
+    T::from_fields(&split_fields(pattern, text)?)
+}
+
+fn split_fields<'a>(pattern: &str, text: &'a str) -> Result<Vec<Field<'a>>, ScanError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    let column = |pos: usize| text[..pos].chars().count() + 1;
+
+    if !text.starts_with(literals[0]) {
+        return Err(ScanError::new(1, format!("expected `{}`", literals[0])));
+    }
+    let mut pos = literals[0].len();
+
+    let mut fields = vec![];
+    for (idx, literal) in literals.iter().enumerate().skip(1) {
+        let is_last = idx + 1 == literals.len();
+        let rest = &text[pos..];
+
+        let end = if literal.is_empty() {
+            assert!(is_last, "scan pattern `{}` has adjacent fields", pattern);
+            text.len()
+        } else if is_last {
+            match rest.strip_suffix(literal) {
+                Some(field) => pos + field.len(),
+                None => {
+                    return Err(ScanError::new(
+                        column(text.len()),
+                        format!("expected line to end with `{}`", literal),
+                    ))
+                }
+            }
+        } else {
+            match rest.find(literal) {
+                Some(found) => pos + found,
+                None => {
+                    return Err(ScanError::new(
+                        column(pos),
+                        format!("expected `{}` after this field", literal),
+                    ))
+                }
+            }
+        };
+
+        fields.push(Field {
+            column: column(pos),
+            text: &text[pos..end],
+        });
+        pos = end + literal.len();
+    }
+
+    if pos < text.len() {
+        return Err(ScanError::new(
+            column(pos),
+            format!("unexpected `{}`", &text[pos..]),
+        ));
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scan::{scan, List, ScanError};
+
+    #[test]
+    fn can_scan_typed_fields() {
+        let pair: (usize, usize, usize, usize) = scan("{}-{},{}-{}", "2-4,6-8").unwrap();
+        let op: (String, i32) = scan("{} {}", "addx -11").unwrap();
+        let items: (List<u64>,) = scan("  Starting items: {}", "  Starting items: 79, 98").unwrap();
+
+        assert_eq!((2, 4, 6, 8), pair);
+        assert_eq!(("addx".to_string(), -11), op);
+        assert_eq!(List(vec![79, 98]), items.0);
+    }
+
+    #[test]
+    fn can_scan_literal_only_patterns() {
+        assert_eq!(Ok(()), scan::<()>("noop", "noop"));
+        assert!(scan::<()>("noop", "noop 1").is_err());
+    }
+
+    #[test]
+    fn mismatches_report_the_column() {
+        let missing_comma = scan::<(u32, u32, u32, u32)>("{}-{},{}-{}", "2-4;6-8").unwrap_err();
+        let bad_number = scan::<(u32, u32, u32, u32)>("{}-{},{}-{}", "2-4,x-8").unwrap_err();
+        let bad_item = scan::<(List<u64>,)>("items: {}", "items: 1, 2, y").unwrap_err();
+
+        assert_eq!(3, missing_comma.column);
+        assert_eq!(5, bad_number.column);
+        assert_eq!(
+            ScanError::new(14, "could not parse `y`: invalid digit found in string"),
+            bad_item
+        );
+    }
+
+    #[test]
+    fn trailing_literals_must_end_the_line() {
+        assert_eq!((3,), scan::<(u8,)>("Monkey {}:", "Monkey 3:").unwrap());
+        assert!(scan::<(u8,)>("Monkey {}:", "Monkey 3").is_err());
+    }
+}
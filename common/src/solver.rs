@@ -0,0 +1,263 @@
+use crate::{Error, InputSource, ReadLines};
+use std::any::Any;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Number(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A day's puzzle: how to turn the raw input into something the two parts can share,
+/// and how to solve each part from it.
+pub trait Solver {
+    const DAY: u8;
+    type Input: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part_one(input: &Self::Input) -> Answer;
+    fn part_two(input: &Self::Input) -> Answer;
+
+    /// Checks the input strictly without solving it, listing every problem found rather
+    /// than stopping at the first. By default this is just the first parse error.
+    fn validate(input: &str) -> Vec<Error> {
+        Self::parse(input).err().into_iter().collect()
+    }
+
+    /// Solves both parts in a single pass over the lines, for inputs too large to load.
+    /// By default the lines are gathered back up and solved as usual.
+    fn stream(lines: ReadLines) -> Result<[Answer; 2], Error> {
+        let mut input = String::new();
+        for line in lines {
+            input.push_str(&line?.text);
+            input.push('\n');
+        }
+
+        let parsed = Self::parse(&input)?;
+        Ok([Self::part_one(&parsed), Self::part_two(&parsed)])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// The answers for one run of a day, along with how long each step took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub day: u8,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+impl Solution {
+    pub fn total_elapsed(&self) -> Duration {
+        self.parse_elapsed + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+}
+
+type ParseFn = fn(&str) -> Result<Parsed, Error>;
+type SolvePartFn = fn(&Parsed, Part) -> PartResult;
+type StreamFn = fn(ReadLines) -> Result<[Answer; 2], Error>;
+type ValidateFn = fn(&str) -> Vec<Error>;
+
+/// A day's parsed input, type-erased so its parts can be solved one at a time, on any thread,
+/// without parsing again.
+pub struct Parsed {
+    pub elapsed: Duration,
+    input: Box<dyn Any + Send + Sync>,
+}
+
+/// Type-erased handle to a `Solver`, so the runner can keep every day in one list.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub day: u8,
+    parse: ParseFn,
+    solve_part: SolvePartFn,
+    stream: StreamFn,
+    validate: ValidateFn,
+}
+
+impl Puzzle {
+    pub fn of<S: Solver>() -> Self {
+        Puzzle {
+            day: S::DAY,
+            parse: parse::<S>,
+            solve_part: solve_part::<S>,
+            stream: S::stream,
+            validate: S::validate,
+        }
+    }
+
+    pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Solution, Error> {
+        let parsed = self.parse(input)?;
+
+        Ok(Solution {
+            day: self.day,
+            parse_elapsed: parsed.elapsed,
+            parts: parts
+                .iter()
+                .map(|part| self.solve_part(&parsed, *part))
+                .collect(),
+        })
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, Error> {
+        (self.parse)(input)
+    }
+
+    /// Solves one part of an input parsed by this puzzle.
+    pub fn solve_part(&self, parsed: &Parsed, part: Part) -> PartResult {
+        (self.solve_part)(parsed, part)
+    }
+
+    pub fn solve_source(&self, source: InputSource, parts: &[Part]) -> Result<Solution, Error> {
+        self.solve(&source.read_to_string()?, parts)
+    }
+
+    pub fn validate(&self, input: &str) -> Vec<Error> {
+        (self.validate)(input)
+    }
+
+    /// Solves while reading the input line by line. Parsing and solving happen together,
+    /// so all of the time is counted as parsing.
+    pub fn solve_stream(&self, source: InputSource, parts: &[Part]) -> Result<Solution, Error> {
+        let start = Instant::now();
+        let [one, two] = (self.stream)(source.lines()?)?;
+        let parse_elapsed = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|part| PartResult {
+                part: *part,
+                answer: match part {
+                    Part::One => one.clone(),
+                    Part::Two => two.clone(),
+                },
+                elapsed: Duration::ZERO,
+            })
+            .collect();
+
+        Ok(Solution {
+            day: self.day,
+            parse_elapsed,
+            parts,
+        })
+    }
+}
+
+fn parse<S: Solver>(input: &str) -> Result<Parsed, Error> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+
+    Ok(Parsed {
+        elapsed: start.elapsed(),
+        input: Box::new(input),
+    })
+}
+
+fn solve_part<S: Solver>(parsed: &Parsed, part: Part) -> PartResult {
+    let input = parsed
+        .input
+        .downcast_ref::<S::Input>()
+        .expect("the input was parsed by the same puzzle");
+
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => S::part_one(input),
+        Part::Two => S::part_two(input),
+    };
+    PartResult {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+pub fn print_answer(part: Part, answer: &Answer) {
+    let rendered = answer.to_string();
+    if rendered.contains('\n') {
+        println!("Part {}:\n{}", part, rendered);
+    } else {
+        println!("Part {}: {}", part, rendered);
+    }
+}
+
+/// Formats a duration with a unit that keeps it readable, e.g. `850ns`, `12.4µs`, `3.10ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    match nanos {
+        0..=999 => format!("{}ns", nanos),
+        1_000..=999_999 => format!("{:.1}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
@@ -0,0 +1,108 @@
+use crate::cli::example_input_path;
+use crate::{Answer, InputSource, Solver};
+use std::fs;
+use std::path::PathBuf;
+#[cfg(feature = "fetch")]
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread::{self, JoinHandle},
+};
+
+/// Reads a day's worked example from `inputs/examples`, wherever the test is run from.
+pub fn example(day: u8) -> String {
+    let path = workspace_root().join(example_input_path(day));
+
+    fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("could not read `{}`: {}", path.display(), error))
+}
+
+pub fn example_input<S: Solver>() -> S::Input {
+    S::parse(&example(S::DAY))
+        .unwrap_or_else(|error| panic!("day {} example does not parse: {}", S::DAY, error))
+}
+
+/// Golden test for a day, solves its example fixture and checks both answers, loaded
+/// whole and streamed line by line.
+pub fn assert_example<S: Solver>(part_one: impl Into<Answer>, part_two: impl Into<Answer>) {
+    let (part_one, part_two) = (part_one.into(), part_two.into());
+    let input = example_input::<S>();
+
+    let example = example(S::DAY);
+    let lines = InputSource::Embedded(&example).lines().unwrap();
+    let streamed = S::stream(lines)
+        .unwrap_or_else(|error| panic!("day {} example does not stream: {}", S::DAY, error));
+    assert_eq!(
+        [part_one.clone(), part_two.clone()],
+        streamed,
+        "day {} streamed",
+        S::DAY
+    );
+
+    assert_eq!(part_one, S::part_one(&input), "day {} part 1", S::DAY);
+    assert_eq!(part_two, S::part_two(&input), "day {} part 2", S::DAY);
+}
+
+/// A stand-in server answering one request per response, it hands back the raw
+/// requests it saw once all responses are sent.
+#[cfg(feature = "fetch")]
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/2022", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = vec![];
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            requests.push(request);
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+        requests
+    });
+
+    (base_url, handle)
+}
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("common lives inside the workspace")
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::example;
+
+    #[test]
+    fn loads_example_fixtures() {
+        assert!(example(1).starts_with("1000\n2000\n3000\n"));
+    }
+}
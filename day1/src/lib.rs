@@ -0,0 +1,121 @@
+use common::{Answer, Error, Line, ReadLines, Solver};
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        common::blocks(input).map(|elf| elf.parse_lines()).collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+
+    fn stream(lines: ReadLines) -> Result<[Answer; 2], Error> {
+        let mut top = [0; 3];
+        let mut total = 0;
+
+        for line in lines {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                record_total(&mut top, total);
+                total = 0;
+            } else {
+                // Trailing whitespace is dropped as it is for the blocks `parse` reads
+                let text = line.text.trim_end();
+                total += Line {
+                    text,
+                    ..line.line()
+                }
+                .parse::<i32>()?;
+            }
+        }
+        record_total(&mut top, total);
+
+        Ok([top[0].into(), top.iter().sum::<i32>().into()])
+    }
+
+    /// Every non-blank line must be a calorie count that is not negative.
+    fn validate(input: &str) -> Vec<Error> {
+        common::numbered_lines(input)
+            .filter(|line| !line.text.trim().is_empty())
+            .filter_map(|line| match line.parse::<i32>() {
+                Ok(calories) if calories < 0 => Some(line.error("negative calorie count")),
+                Ok(_) => None,
+                Err(error) => Some(error),
+            })
+            .collect()
+    }
+}
+
+fn part_one(input: &[Vec<i32>]) -> i32 {
+    top_totals(input)[0]
+}
+
+fn part_two(input: &[Vec<i32>]) -> i32 {
+    top_totals(input).iter().sum()
+}
+
+fn top_totals(input: &[Vec<i32>]) -> [i32; 3] {
+    let mut top = [0; 3];
+    for elf in input {
+        record_total(&mut top, elf.iter().sum());
+    }
+    top
+}
+
+/// Keeps the three highest totals seen so far, largest first.
+fn record_total(top: &mut [i32; 3], total: i32) {
+    if let Some(idx) = top.iter().position(|&t| total > t) {
+        top[idx..].rotate_right(1);
+        top[idx] = total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{part_two, Day1};
+    use common::{Answer, InputSource, Solver};
+
+    #[test]
+    fn example_answers() {
+        common::testing::assert_example::<Day1>(24000, 45000);
+    }
+
+    #[test]
+    fn last_elf_counts_without_trailing_blank_line() {
+        let input = Day1::parse("1\n2\n\n10\n\n3\n\n40").unwrap();
+
+        assert_eq!(vec![vec![1, 2], vec![10], vec![3], vec![40]], input);
+        assert_eq!(53, part_two(&input));
+    }
+
+    #[test]
+    fn streaming_ignores_trailing_whitespace() {
+        let input = "1 \r\n2\t\n\n10  \n";
+        let lines = InputSource::Embedded(input).lines().unwrap();
+
+        assert_eq!(
+            [Answer::from(10), Answer::from(13)],
+            Day1::stream(lines).unwrap()
+        );
+        assert_eq!(2, Day1::parse(input).unwrap().len());
+    }
+
+    #[test]
+    fn validate_lists_every_bad_line() {
+        let errors = Day1::validate("100\n-5\n\n20\nten\n\n3\n");
+
+        assert_eq!(2, errors.len());
+        assert!(errors[0].to_string().contains("line 2"), "{}", errors[0]);
+        assert!(errors[1].to_string().contains("line 5"), "{}", errors[1]);
+        assert!(Day1::validate("1\n2\n\n3").is_empty());
+    }
+}
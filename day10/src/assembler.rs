@@ -0,0 +1,560 @@
+//! Assembler for CPU programs. Puzzle input is already valid assembly, and on top of it
+//! programs can use `;` comments, blank lines, labels, constants and macros:
+//!
+//! ```text
+//! .const STEP 2        ; a number usable wherever one is expected, `-STEP` negates it
+//!
+//! .macro shift by      ; a macro with one parameter, `-by` negates it
+//!     addx by
+//!     noop
+//! .endm
+//!
+//!     set y 3
+//! again:               ; a label names the instruction after it
+//!     shift STEP
+//!     sub y 1
+//!     jnz y again      ; jumps take a label or a relative offset
+//! ```
+//!
+//! Constants, labels and macros share one namespace, and none of them can be named after a
+//! register or an instruction.
+
+use crate::{Operand, OperandKind, Register, INSTRUCTIONS, OP};
+use common::{Error, Line};
+use std::collections::HashMap;
+
+/// How deep macros can call other macros, which also stops a macro calling itself.
+const MACRO_DEPTH: usize = 16;
+
+/// A word of the source, with where it came from for errors.
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    line: Line<'a>,
+    column: usize,
+    /// Passed to a macro parameter used as `-param`.
+    negated: bool,
+}
+
+impl Token<'_> {
+    fn error(&self, reason: impl std::fmt::Display) -> Error {
+        self.line.error_at(self.column, reason)
+    }
+}
+
+/// Splits a line into words, dropping any comment.
+fn tokens(line: Line) -> Vec<Token> {
+    let code = line.text.split(';').next().unwrap_or("");
+    let mut tokens = vec![];
+    let mut start = None;
+
+    for (column, (pos, c)) in code.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, pos)),
+            (true, Some((column, from))) => {
+                tokens.push(Token {
+                    text: &code[from..pos],
+                    line,
+                    column,
+                    negated: false,
+                });
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((column, from)) = start {
+        tokens.push(Token {
+            text: &code[from..],
+            line,
+            column,
+            negated: false,
+        });
+    }
+    tokens
+}
+
+struct Macro<'a> {
+    params: Vec<&'a str>,
+    body: Vec<Line<'a>>,
+}
+
+enum Name<'a> {
+    Constant(i32),
+    /// Index of the instruction the label is on.
+    Label(usize),
+    Macro(Macro<'a>),
+}
+
+struct Statement<'a> {
+    mnemonic: Token<'a>,
+    operands: Vec<Token<'a>>,
+    /// Line of the macro call this was expanded from.
+    called_from: Option<usize>,
+}
+
+#[derive(Default)]
+struct Assembler<'a> {
+    names: HashMap<&'a str, Name<'a>>,
+    statements: Vec<Statement<'a>>,
+}
+
+/// Assembles a program into instructions, reporting the first error.
+pub fn assemble(input: &str) -> Result<Vec<OP>, Error> {
+    assemble_all(input).map_err(|mut errors| errors.remove(0))
+}
+
+/// Assembles a program, reporting every error. Instructions are only checked once the
+/// definitions are free of errors, as a broken one would make every use of it an error too.
+pub(crate) fn assemble_all(input: &str) -> Result<Vec<OP>, Vec<Error>> {
+    let mut assembler = Assembler::default();
+    let mut lines = common::numbered_lines(input);
+    let mut errors = vec![];
+
+    while let Some(line) = lines.next() {
+        if let Err(error) = assembler.line(line, &mut lines) {
+            errors.push(error);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    assembler.finish()
+}
+
+impl<'a> Assembler<'a> {
+    /// Reads one line, and for a macro the lines up to its `.endm`.
+    fn line(
+        &mut self,
+        line: Line<'a>,
+        lines: &mut impl Iterator<Item = Line<'a>>,
+    ) -> Result<(), Error> {
+        let mut words = tokens(line);
+        let Some(first) = words.first().copied() else {
+            return Ok(());
+        };
+
+        match first.text {
+            ".const" => self.constant(&words),
+            ".macro" => self.define_macro(&words, lines),
+            ".endm" => Err(first.error("`.endm` without a `.macro`")),
+            directive if directive.starts_with('.') => {
+                Err(first.error(format!("unknown directive `{}`", directive)))
+            }
+            label if label.ends_with(':') => {
+                let name = label.trim_end_matches(':');
+                let index = self.statements.len();
+                self.define(first, name, Name::Label(index))?;
+
+                words.remove(0);
+                match words.is_empty() {
+                    true => Ok(()),
+                    false => self.statement(words, None, 0),
+                }
+            }
+            _ => self.statement(words, None, 0),
+        }
+    }
+
+    fn define(&mut self, token: Token<'a>, name: &'a str, value: Name<'a>) -> Result<(), Error> {
+        check_name(token, name)?;
+        if self.names.insert(name, value).is_some() {
+            return Err(token.error(format!("`{}` is already defined", name)));
+        }
+        Ok(())
+    }
+
+    /// `.const NAME value`, where the value is a number or an earlier constant.
+    fn constant(&mut self, words: &[Token<'a>]) -> Result<(), Error> {
+        let [_, name, value] = words else {
+            return Err(words[0].error("expected `.const <name> <value>`"));
+        };
+        let value = match self.resolve(value, OperandKind::Value, 0)? {
+            Operand::Value(value) => value,
+            Operand::Register(_) => return Err(value.error("a constant must be a number")),
+        };
+        self.define(*name, name.text, Name::Constant(value))
+    }
+
+    /// `.macro NAME params...` up to `.endm`, the body is expanded where the macro is used.
+    fn define_macro(
+        &mut self,
+        words: &[Token<'a>],
+        lines: &mut impl Iterator<Item = Line<'a>>,
+    ) -> Result<(), Error> {
+        let [directive, name, params @ ..] = words else {
+            return Err(words[0].error("expected `.macro <name> [params...]`"));
+        };
+
+        let mut body = vec![];
+        loop {
+            let Some(line) = lines.next() else {
+                return Err(directive.error("`.macro` without an `.endm`"));
+            };
+            let Some(first) = tokens(line).first().copied() else {
+                continue;
+            };
+            if first.text == ".endm" {
+                break;
+            }
+            if first.text.starts_with('.') || first.text.ends_with(':') {
+                return Err(first.error("macros can only contain instructions and macro calls"));
+            }
+            body.push(line);
+        }
+
+        for (idx, param) in params.iter().enumerate() {
+            check_name(*param, param.text)?;
+            if params[..idx].iter().any(|other| other.text == param.text) {
+                return Err(param.error(format!("parameter `{}` is given twice", param.text)));
+            }
+        }
+
+        let params = params.iter().map(|param| param.text).collect();
+        self.define(*name, name.text, Name::Macro(Macro { params, body }))
+    }
+
+    /// An instruction, or a macro call which is expanded in place.
+    fn statement(
+        &mut self,
+        words: Vec<Token<'a>>,
+        called_from: Option<usize>,
+        depth: usize,
+    ) -> Result<(), Error> {
+        let (mnemonic, args) = (words[0], &words[1..]);
+        let Some(Name::Macro(definition)) = self.names.get(mnemonic.text) else {
+            self.statements.push(Statement {
+                mnemonic,
+                operands: args.to_vec(),
+                called_from,
+            });
+            return Ok(());
+        };
+
+        if depth == MACRO_DEPTH {
+            let error = mnemonic.error("macros are nested too deeply");
+            return Err(in_macro(error, called_from));
+        }
+        if args.len() != definition.params.len() {
+            let error = mnemonic.error(format!(
+                "`{}` takes {} arguments, found {}",
+                mnemonic.text,
+                definition.params.len(),
+                args.len()
+            ));
+            return Err(in_macro(error, called_from));
+        }
+
+        // Arguments keep their own position, so errors in them point at the call
+        let expanded: Vec<Vec<Token<'a>>> = definition
+            .body
+            .iter()
+            .map(|line| {
+                tokens(*line)
+                    .into_iter()
+                    .map(|token| {
+                        let (negated, name) = match token.text.strip_prefix('-') {
+                            Some(name) => (true, name),
+                            None => (false, token.text),
+                        };
+                        match definition.params.iter().position(|p| *p == name) {
+                            Some(idx) => Token {
+                                negated: args[idx].negated != negated,
+                                ..args[idx]
+                            },
+                            None => token,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let called_from = called_from.or(Some(mnemonic.line.number));
+        for words in expanded {
+            self.statement(words, called_from, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Vec<OP>, Vec<Error>> {
+        let mut ops = vec![];
+        let mut errors = vec![];
+
+        for (idx, statement) in self.statements.iter().enumerate() {
+            match self.instruction(idx, statement) {
+                Ok(op) => ops.push(op),
+                Err(error) => errors.push(in_macro(error, statement.called_from)),
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(ops),
+            false => Err(errors),
+        }
+    }
+
+    fn instruction(&self, idx: usize, statement: &Statement<'a>) -> Result<OP, Error> {
+        let mnemonic = statement.mnemonic;
+        let Some(instruction) = INSTRUCTIONS.iter().find(|i| i.mnemonic == mnemonic.text) else {
+            return Err(mnemonic.error(format!("unknown instruction `{}`", mnemonic.text)));
+        };
+
+        let expected = instruction.operands.len();
+        if let Some(extra) = statement.operands.get(expected) {
+            return Err(extra.error(format!("unexpected `{}`", extra.text)));
+        }
+        if statement.operands.len() < expected {
+            return Err(mnemonic.error(format!(
+                "`{}` takes {} operands, found {}",
+                mnemonic.text,
+                expected,
+                statement.operands.len()
+            )));
+        }
+
+        let operands = instruction
+            .operands
+            .iter()
+            .zip(&statement.operands)
+            .map(|(kind, token)| {
+                let operand = self.resolve(token, *kind, idx)?;
+                match kind.accepts(&operand) {
+                    true => Ok(operand),
+                    false => Err(token.error(format!("`{}` takes {} here", mnemonic.text, kind))),
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok((instruction.build)(&operands))
+    }
+
+    /// A register, a number, a constant, or for offsets a label.
+    fn resolve(&self, token: &Token, kind: OperandKind, idx: usize) -> Result<Operand, Error> {
+        match (token.negated, self.resolve_text(token, kind, idx)?) {
+            (false, operand) => Ok(operand),
+            (true, Operand::Value(value)) => negate(token, value).map(Operand::Value),
+            (true, Operand::Register(_)) => {
+                Err(token.error(format!("register `{}` cannot be negated", token.text)))
+            }
+        }
+    }
+
+    fn resolve_text(&self, token: &Token, kind: OperandKind, idx: usize) -> Result<Operand, Error> {
+        if let Some(register) = register(token.text) {
+            return Ok(Operand::Register(register));
+        }
+        if let Ok(value) = token.text.parse() {
+            return Ok(Operand::Value(value));
+        }
+
+        let (negated, name) = match token.text.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, token.text),
+        };
+        let value = match self.names.get(name) {
+            Some(Name::Constant(value)) => *value,
+            Some(Name::Label(target)) if kind == OperandKind::Offset => *target as i32 - idx as i32,
+            Some(Name::Label(_)) => {
+                return Err(token.error(format!("label `{}` can only be a jump target", name)))
+            }
+            Some(Name::Macro(_)) => return Err(token.error(format!("`{}` is a macro", name))),
+            None if name.starts_with(|c: char| c.is_ascii_digit()) => {
+                return Err(token.error(format!("could not parse `{}`", token.text)))
+            }
+            None => return Err(token.error(format!("unknown constant or label `{}`", name))),
+        };
+
+        match negated {
+            true => negate(token, value).map(Operand::Value),
+            false => Ok(Operand::Value(value)),
+        }
+    }
+}
+
+fn negate(token: &Token, value: i32) -> Result<i32, Error> {
+    value
+        .checked_neg()
+        .ok_or_else(|| token.error(format!("`{}` cannot be negated, it is too large", value)))
+}
+
+/// Names are letters, digits and `_`, not starting with a digit, and cannot be taken from a
+/// register or an instruction.
+fn check_name(token: Token, name: &str) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if !valid {
+        return Err(token.error(format!("`{}` is not a valid name", name)));
+    }
+    if register(name).is_some() {
+        return Err(token.error(format!("`{}` is a register", name)));
+    }
+    if INSTRUCTIONS.iter().any(|i| i.mnemonic == name) {
+        return Err(token.error(format!("`{}` is an instruction", name)));
+    }
+    Ok(())
+}
+
+fn register(name: &str) -> Option<Register> {
+    match name {
+        "x" => Some(Register::X),
+        "y" => Some(Register::Y),
+        "z" => Some(Register::Z),
+        _ => None,
+    }
+}
+
+/// Errors in a macro body point at the body, so say where it was called from.
+fn in_macro(error: Error, called_from: Option<usize>) -> Error {
+    match (error, called_from) {
+        (
+            Error::Parse {
+                line,
+                column,
+                text,
+                reason,
+            },
+            Some(call),
+        ) => Error::Parse {
+            line,
+            column,
+            text,
+            reason: format!("{} (in macro called on line {})", reason, call),
+        },
+        (error, _) => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assembler::{assemble, assemble_all};
+    use crate::{Operand, Register, OP};
+
+    fn error(program: &str) -> String {
+        assemble(program).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn resolves_labels_constants_and_macros() {
+        let program = "
+; count y down from 3
+.const STEP 2
+
+.macro shift by
+    addx by
+    noop
+.endm
+
+    set y 3
+again:  shift -STEP     ; two instructions
+    sub y 1
+    jnz y again
+done: jmp done
+";
+        assert_eq!(
+            vec![
+                OP::Set(Register::Y, Operand::Value(3)),
+                OP::Addx(-2),
+                OP::NOOP,
+                OP::Sub(Register::Y, Operand::Value(1)),
+                OP::Jnz(Register::Y, -3),
+                OP::Jmp(0),
+            ],
+            assemble(program).unwrap()
+        );
+    }
+
+    #[test]
+    fn puzzle_input_assembles_unchanged() {
+        let input = common::testing::example(10);
+        let ops: Vec<OP> = common::parse_lines(&input).unwrap();
+        assert_eq!(ops, assemble(&input).unwrap());
+    }
+
+    #[test]
+    fn reports_where_errors_are() {
+        assert_eq!(
+            "line 2, column 9: label `top` can only be a jump target (`   addx top`)",
+            error("top:\n   addx top")
+        );
+        assert_eq!(
+            "line 2, column 8: `N` is already defined (`.const N 2`)",
+            error(".const N 1\n.const N 2")
+        );
+        assert_eq!(
+            "line 1, column 1: `x` is a register (`x: noop`)",
+            error("x: noop")
+        );
+        assert_eq!(
+            "line 1, column 6: unexpected `2` (`noop 2 ; no operands`)",
+            error("noop 2 ; no operands")
+        );
+        assert_eq!(
+            "line 1, column 1: `.macro` without an `.endm` (`.macro m`)",
+            error(".macro m\nnoop")
+        );
+        assert_eq!(
+            "line 1, column 1: `.endm` without a `.macro` (`.endm`)",
+            error(".endm")
+        );
+    }
+
+    #[test]
+    fn errors_in_macros_name_the_call() {
+        let program = ".macro load r\n  set r 1\n.endm\nnoop\nload 4\n";
+        assert_eq!(
+            "line 5, column 6: `set` takes a register (x, y or z) here (in macro called on line 5) (`load 4`)",
+            error(program)
+        );
+
+        let program = ".macro bad\n  divx 1\n.endm\nbad\n";
+        assert_eq!(
+            "line 2, column 3: unknown instruction `divx` (in macro called on line 4) (`  divx 1`)",
+            error(program)
+        );
+
+        let program = ".macro loop\n  loop\n.endm\nloop\n";
+        assert!(error(program).contains("macros are nested too deeply"));
+    }
+
+    #[test]
+    fn macro_parameters_can_be_negated() {
+        let program = ".const STEP 2\n.macro back by\n  addx -by\n.endm\nback 3\nback -STEP\n";
+        assert_eq!(vec![OP::Addx(-3), OP::Addx(2)], assemble(program).unwrap());
+
+        assert_eq!(
+            "line 5, column 6: register `y` cannot be negated (in macro called on line 5) (`back y`)",
+            error(".macro back r\n  set -r 1\n.endm\nnoop\nback y\n")
+        );
+    }
+
+    #[test]
+    fn bad_macro_parameters_are_rejected() {
+        assert_eq!(
+            "line 1, column 12: parameter `a` is given twice (`.macro m a a`)",
+            error(".macro m a a\n  addx a\n.endm")
+        );
+        assert_eq!(
+            "line 1, column 10: `x` is a register (`.macro m x`)",
+            error(".macro m x\n  addx x\n.endm")
+        );
+    }
+
+    #[test]
+    fn negating_the_smallest_number_is_an_error() {
+        assert_eq!(
+            "line 2, column 6: `-2147483648` cannot be negated, it is too large (`addx -MIN`)",
+            error(".const MIN -2147483648\naddx -MIN")
+        );
+    }
+
+    #[test]
+    fn lists_every_bad_instruction() {
+        let errors = assemble_all("noop\ndivx 3\nlabel: addx nowhere\njmp label\n").unwrap_err();
+        assert_eq!(2, errors.len());
+        assert!(errors[1].to_string().starts_with("line 3, column 13"));
+    }
+}
@@ -0,0 +1,424 @@
+//! A step debugger for the CPU, started with `day10 --debug [input]`. Commands are read from
+//! stdin, see `HELP`.
+
+use crate::{Day10, Program, Register, OP};
+use common::cli::{self, InputArg};
+use common::{Args, Error, Solver};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "commands:
+    step [n]              run n cycles (1)
+    next [n]              run until n more instructions have completed (1)
+    continue              run until a breakpoint or the end of the program
+    break pc <n>          stop before instruction n, as numbered by --list
+    break cycle <n>       stop before cycle n
+    break x|y|z <n>       stop when a register changes to n
+    delete <n>            remove breakpoint n
+    breakpoints           list breakpoints
+    regs                  print the cycle, instruction, registers and signal strength
+    row                   print the CRT row being drawn, with the beam and sprite
+    history [n]           print the last n completed instructions (10)
+    help
+    quit
+
+An empty line repeats the last command. The cycle shown is the one about to run,
+so X is its value during that cycle.";
+
+/// Completed instructions kept for `history`.
+const HISTORY_LIMIT: usize = 1000;
+
+/// Cycles a single command may run, so a program that jumps forever does not hang.
+const RUN_LIMIT: usize = 1_000_000;
+
+const REGISTERS: [Register; 3] = [Register::X, Register::Y, Register::Z];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Before instruction `n` of the assembled program starts, counted from 1. Comments,
+    /// labels and macros make it differ from the source line, `day10 --list` shows it.
+    Pc(usize),
+    /// Before this cycle runs.
+    Cycle(i32),
+    /// When the register changes to this value.
+    Register(Register, i32),
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::Pc(pc) => write!(f, "pc {}", pc),
+            Breakpoint::Cycle(cycle) => write!(f, "cycle {}", cycle),
+            Breakpoint::Register(register, value) => write!(f, "{} {}", register, value),
+        }
+    }
+}
+
+/// A completed instruction.
+#[derive(Debug, Clone, Copy)]
+struct Executed {
+    pc: usize,
+    op: OP,
+    first_cycle: i32,
+    x_after: i32,
+}
+
+pub struct Debugger {
+    ops: Vec<OP>,
+    program: Program,
+    executed: usize,
+    breakpoints: Vec<Breakpoint>,
+    history: VecDeque<Executed>,
+}
+
+impl Debugger {
+    pub fn new(ops: Vec<OP>) -> Self {
+        Debugger {
+            ops,
+            program: Program::new(),
+            executed: 0,
+            breakpoints: vec![],
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Reads commands until `quit` or the end of `input`.
+    pub fn repl(&mut self, input: impl BufRead, mut out: impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} instructions loaded, `help` lists commands",
+            self.ops.len()
+        )?;
+        self.regs(&mut out)?;
+
+        let mut last = String::new();
+        let mut lines = input.lines();
+        loop {
+            write!(out, "> ")?;
+            out.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                return Ok(());
+            };
+
+            let command = match line.trim() {
+                "" => last.clone(),
+                command => command.to_string(),
+            };
+            if !self.execute(&command, &mut out)? {
+                return Ok(());
+            }
+            last = command;
+        }
+    }
+
+    /// Runs one command, returns `false` for `quit`.
+    pub fn execute(&mut self, command: &str, out: &mut impl Write) -> io::Result<bool> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let count = |idx: usize, default: usize| match words.get(idx) {
+            Some(word) => word.parse::<usize>().ok().filter(|n| *n > 0),
+            None => Some(default),
+        };
+
+        match words.as_slice() {
+            [] => (),
+            ["s" | "step", ..] => match count(1, 1) {
+                Some(mut left) => self.run(out, |_| {
+                    left -= 1;
+                    left == 0
+                })?,
+                None => writeln!(out, "step takes a number of cycles")?,
+            },
+            ["n" | "next", ..] => match count(1, 1) {
+                Some(n) => {
+                    let target = self.executed + n;
+                    self.run(out, |debugger| debugger.executed >= target)?
+                }
+                None => writeln!(out, "next takes a number of instructions")?,
+            },
+            ["c" | "continue"] => self.run(out, |_| false)?,
+            ["b" | "break", kind, value] => match parse_breakpoint(kind, value) {
+                Some(breakpoint) => {
+                    self.breakpoints.push(breakpoint);
+                    writeln!(out, "breakpoint {}: {}", self.breakpoints.len(), breakpoint)?;
+                }
+                None => writeln!(out, "expected `break pc|cycle|x|y|z <n>`")?,
+            },
+            ["d" | "delete", n] => match n.parse::<usize>() {
+                Ok(n) if (1..=self.breakpoints.len()).contains(&n) => {
+                    let breakpoint = self.breakpoints.remove(n - 1);
+                    writeln!(out, "deleted breakpoint {}: {}", n, breakpoint)?;
+                }
+                _ => writeln!(out, "there is no breakpoint {}", n)?,
+            },
+            ["bl" | "breakpoints"] => {
+                if self.breakpoints.is_empty() {
+                    writeln!(out, "no breakpoints")?;
+                }
+                for (idx, breakpoint) in self.breakpoints.iter().enumerate() {
+                    writeln!(out, "{}: {}", idx + 1, breakpoint)?;
+                }
+            }
+            ["r" | "regs"] => self.regs(out)?,
+            ["row"] => self.row(out)?,
+            ["h" | "history", ..] => match count(1, 10) {
+                Some(n) => self.history(n, out)?,
+                None => writeln!(out, "history takes a number of instructions")?,
+            },
+            ["help"] => writeln!(out, "{}", HELP)?,
+            ["q" | "quit"] => return Ok(false),
+            _ => writeln!(out, "unknown command `{}`, try `help`", command)?,
+        }
+        Ok(true)
+    }
+
+    /// Runs one cycle of the current instruction, `false` once the program has finished.
+    fn step_cycle(&mut self) -> bool {
+        let Some((idx, op)) = self.program.tick(&self.ops) else {
+            return false;
+        };
+
+        if self.program.op_cycle == 0 {
+            if self.history.len() == HISTORY_LIMIT {
+                self.history.pop_front();
+            }
+            self.history.push_back(Executed {
+                pc: idx + 1,
+                op,
+                first_cycle: self.program.pc - op.cycles() as i32,
+                x_after: self.program.x_reg,
+            });
+            self.executed += 1;
+        }
+        true
+    }
+
+    fn registers(&self) -> [i32; 3] {
+        REGISTERS.map(|register| self.program.read(register))
+    }
+
+    /// The first breakpoint matching the state after a cycle that started with `before`.
+    fn hit(&self, before: [i32; 3]) -> Option<Breakpoint> {
+        let program = &self.program;
+        self.breakpoints
+            .iter()
+            .copied()
+            .find(|breakpoint| match *breakpoint {
+                Breakpoint::Pc(pc) => program.op_cycle == 0 && program.ip.wrapping_add(1) == pc,
+                Breakpoint::Cycle(cycle) => program.pc == cycle,
+                Breakpoint::Register(register, value) => {
+                    let idx = REGISTERS.iter().position(|r| *r == register).unwrap();
+                    program.read(register) == value && before[idx] != value
+                }
+            })
+    }
+
+    /// Runs at least one cycle, until `done`, a breakpoint or the end of the program.
+    fn run(&mut self, out: &mut impl Write, mut done: impl FnMut(&Self) -> bool) -> io::Result<()> {
+        for cycles in 1.. {
+            let before = self.registers();
+            if !self.step_cycle() {
+                break;
+            }
+            if let Some(breakpoint) = self.hit(before) {
+                writeln!(out, "stopped at {}", breakpoint)?;
+                break;
+            }
+            if self.program.finished(&self.ops) || done(self) {
+                break;
+            }
+            if cycles == RUN_LIMIT {
+                writeln!(
+                    out,
+                    "stopped after {} cycles, the program may loop forever",
+                    RUN_LIMIT
+                )?;
+                break;
+            }
+        }
+        self.regs(out)
+    }
+
+    fn regs(&self, out: &mut impl Write) -> io::Result<()> {
+        let program = &self.program;
+        let position = match self.ops.get(program.ip) {
+            Some(op) => format!(
+                "pc {} `{}` (cycle {} of {})",
+                program.ip + 1,
+                op,
+                program.op_cycle + 1,
+                op.cycles()
+            ),
+            None => format!("finished after {} cycles", program.pc - 1),
+        };
+
+        writeln!(
+            out,
+            "cycle {}  {}  x {}  y {}  z {}  signal strength {}",
+            program.pc,
+            position,
+            program.x_reg,
+            program.y_reg,
+            program.z_reg,
+            program.signal_strength
+        )
+    }
+
+    fn row(&self, out: &mut impl Write) -> io::Result<()> {
+        let program = &self.program;
+        let crt = &program.pixel_buffer;
+        let row = (program.pc - 1) as usize / crt.width();
+        if row >= crt.height() {
+            return writeln!(out, "the beam is past the last row");
+        }
+
+        let line: String = crt.row(row).iter().collect();
+        let sprite: String = (0..crt.width() as i32)
+            .map(|col| match (col - program.x_reg).abs() <= 1 {
+                true => '#',
+                false => ' ',
+            })
+            .collect();
+
+        writeln!(out, "row {:<3} {}", row, line)?;
+        writeln!(out, "beam    {:>1$}", '^', program.cycle_pc as usize + 1)?;
+        writeln!(out, "sprite  {}", sprite.trim_end())
+    }
+
+    fn history(&self, count: usize, out: &mut impl Write) -> io::Result<()> {
+        if self.history.is_empty() {
+            return writeln!(out, "no instructions have completed");
+        }
+
+        let skip = self.history.len().saturating_sub(count);
+        for executed in self.history.iter().skip(skip) {
+            writeln!(
+                out,
+                "pc {:<5} cycle {:<5} {:<10} x {}",
+                executed.pc, executed.first_cycle, executed.op, executed.x_after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_breakpoint(kind: &str, value: &str) -> Option<Breakpoint> {
+    match kind {
+        "pc" => value.parse().ok().map(Breakpoint::Pc),
+        "cycle" => value.parse().ok().map(Breakpoint::Cycle),
+        "x" | "y" | "z" => {
+            let register = match kind {
+                "x" => Register::X,
+                "y" => Register::Y,
+                _ => Register::Z,
+            };
+            value
+                .parse()
+                .ok()
+                .map(|value| Breakpoint::Register(register, value))
+        }
+        _ => None,
+    }
+}
+
+/// `day10 --debug [options] [input]`.
+pub fn main(args: Vec<String>) {
+    if let Err(error) = try_main(args) {
+        common::exit_with(&error);
+    }
+}
+
+fn try_main(args: Vec<String>) -> Result<(), Error> {
+    let args = Args::from_args(args.into_iter().filter(|arg| arg != "--debug"))?;
+    if args.help {
+        println!(
+            "usage: day10 --debug [options] [input]\n\n{}\n\n{}",
+            cli::OPTIONS_HELP,
+            HELP
+        );
+        return Ok(());
+    }
+    if args.input == InputArg::Stdin {
+        return Err(Error::argument(
+            "the debugger reads commands from stdin, give the program as a file",
+        ));
+    }
+
+    let ops = Day10::parse(&args.load_input(Day10::DAY)?)?;
+    Debugger::new(ops)
+        .repl(io::stdin().lock(), io::stdout().lock())
+        .map_err(|error| Error::write("stdout", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::debugger::Debugger;
+    use crate::Day10;
+    use common::Solver;
+
+    fn session(program: &str, commands: &[&str]) -> String {
+        let mut debugger = Debugger::new(Day10::parse(program).unwrap());
+        let mut out = vec![];
+        for command in commands {
+            debugger.execute(command, &mut out).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn steps_cycles_and_instructions() {
+        let output = session("noop\naddx 3\naddx -5\n", &["step", "step", "next", "next"]);
+
+        assert_eq!(
+            "cycle 2  pc 2 `addx 3` (cycle 1 of 2)  x 1  y 0  z 0  signal strength 0
+cycle 3  pc 2 `addx 3` (cycle 2 of 2)  x 1  y 0  z 0  signal strength 0
+cycle 4  pc 3 `addx -5` (cycle 1 of 2)  x 4  y 0  z 0  signal strength 0
+cycle 6  finished after 5 cycles  x -1  y 0  z 0  signal strength 0
+",
+            output
+        );
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let program = common::testing::example(10);
+        let output = session(
+            &program,
+            &["break cycle 20", "break x 9", "continue", "continue"],
+        );
+        let stops: Vec<&str> = output.lines().filter(|l| l.starts_with("stop")).collect();
+
+        assert_eq!(vec!["stopped at cycle 20", "stopped at x 9"], stops);
+        assert!(output.contains(
+            "cycle 20  pc 11 `addx -1` (cycle 1 of 2)  x 21  y 0  z 0  signal strength 420"
+        ));
+
+        let output = session(&program, &["break pc 3", "continue", "history 2"]);
+        assert!(output.ends_with(
+            "stopped at pc 3
+cycle 5  pc 3 `addx 6` (cycle 1 of 2)  x 5  y 0  z 0  signal strength 0
+pc 1     cycle 1     addx 15    x 16
+pc 2     cycle 3     addx -11   x 5
+"
+        ));
+    }
+
+    #[test]
+    fn shows_the_crt_row_being_drawn() {
+        let output = session(&common::testing::example(10), &["step 7", "row"]);
+
+        assert!(output.ends_with(
+            "row 0   ##..##..................................
+beam           ^
+sprite            ###
+"
+        ));
+    }
+
+    #[test]
+    fn endless_loops_do_not_hang() {
+        let output = session("jmp 0\n", &["break y 1", "continue"]);
+
+        assert!(output.starts_with("breakpoint 1: y 1\nstopped after 1000000 cycles"));
+    }
+}
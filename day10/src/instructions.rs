@@ -0,0 +1,368 @@
+//! The CPU's instruction set. Each instruction's mnemonic, operand kinds and cycle cost are
+//! declared once in a row of `INSTRUCTIONS`, which parsing, timing and printing all read from
+//! through `OP::instruction`. What it does is in `Program::complete`.
+
+use common::ScanError;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    X,
+    Y,
+    Z,
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Register::X => write!(f, "x"),
+            Register::Y => write!(f, "y"),
+            Register::Z => write!(f, "z"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Value(i32),
+    Register(Register),
+}
+
+impl Operand {
+    fn value(&self) -> i32 {
+        match self {
+            Operand::Value(value) => *value,
+            Operand::Register(_) => unreachable!("operand kinds are checked while parsing"),
+        }
+    }
+
+    fn register(&self) -> Register {
+        match self {
+            Operand::Register(register) => *register,
+            Operand::Value(_) => unreachable!("operand kinds are checked while parsing"),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(Operand::Register(Register::X)),
+            "y" => Ok(Operand::Register(Register::Y)),
+            "z" => Ok(Operand::Register(Register::Z)),
+            _ => s.parse().map(Operand::Value),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Value(value) => write!(f, "{}", value),
+            Operand::Register(register) => write!(f, "{}", register),
+        }
+    }
+}
+
+/// What an instruction accepts in each operand position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    Value,
+    Register,
+    /// A number or a register to read.
+    Source,
+    /// How many instructions to jump, counted from the jump itself.
+    Offset,
+}
+
+impl OperandKind {
+    pub(crate) fn accepts(&self, operand: &Operand) -> bool {
+        matches!(
+            (self, operand),
+            (OperandKind::Value | OperandKind::Offset, Operand::Value(_))
+                | (OperandKind::Register, Operand::Register(_))
+                | (OperandKind::Source, _)
+        )
+    }
+}
+
+impl fmt::Display for OperandKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OperandKind::Value => write!(f, "a number"),
+            OperandKind::Register => write!(f, "a register (x, y or z)"),
+            OperandKind::Source => write!(f, "a number or a register"),
+            OperandKind::Offset => write!(f, "an offset"),
+        }
+    }
+}
+
+pub struct Instruction {
+    pub mnemonic: &'static str,
+    pub operands: &'static [OperandKind],
+    pub cycles: u32,
+    /// Builds the instruction from operands already checked against `operands`.
+    pub(crate) build: fn(&[Operand]) -> OP,
+}
+
+use OperandKind::{Offset, Register as Reg, Source, Value};
+
+const NOOP: Instruction = Instruction {
+    mnemonic: "noop",
+    operands: &[],
+    cycles: 1,
+    build: |_| OP::NOOP,
+};
+
+const ADDX: Instruction = Instruction {
+    mnemonic: "addx",
+    operands: &[Value],
+    cycles: 2,
+    build: |ops| OP::Addx(ops[0].value()),
+};
+
+const SUBX: Instruction = Instruction {
+    mnemonic: "subx",
+    operands: &[Value],
+    cycles: 2,
+    build: |ops| OP::Subx(ops[0].value()),
+};
+
+const MULX: Instruction = Instruction {
+    mnemonic: "mulx",
+    operands: &[Value],
+    cycles: 3,
+    build: |ops| OP::Mulx(ops[0].value()),
+};
+
+const SET: Instruction = Instruction {
+    mnemonic: "set",
+    operands: &[Reg, Source],
+    cycles: 1,
+    build: |ops| OP::Set(ops[0].register(), ops[1]),
+};
+
+const ADD: Instruction = Instruction {
+    mnemonic: "add",
+    operands: &[Reg, Source],
+    cycles: 2,
+    build: |ops| OP::Add(ops[0].register(), ops[1]),
+};
+
+const SUB: Instruction = Instruction {
+    mnemonic: "sub",
+    operands: &[Reg, Source],
+    cycles: 2,
+    build: |ops| OP::Sub(ops[0].register(), ops[1]),
+};
+
+const MUL: Instruction = Instruction {
+    mnemonic: "mul",
+    operands: &[Reg, Source],
+    cycles: 3,
+    build: |ops| OP::Mul(ops[0].register(), ops[1]),
+};
+
+const JMP: Instruction = Instruction {
+    mnemonic: "jmp",
+    operands: &[Offset],
+    cycles: 1,
+    build: |ops| OP::Jmp(ops[0].value()),
+};
+
+const JZ: Instruction = Instruction {
+    mnemonic: "jz",
+    operands: &[Reg, Offset],
+    cycles: 2,
+    build: |ops| OP::Jz(ops[0].register(), ops[1].value()),
+};
+
+const JNZ: Instruction = Instruction {
+    mnemonic: "jnz",
+    operands: &[Reg, Offset],
+    cycles: 2,
+    build: |ops| OP::Jnz(ops[0].register(), ops[1].value()),
+};
+
+/// Every instruction, in the order `OP` declares them.
+pub const INSTRUCTIONS: &[Instruction] =
+    &[NOOP, ADDX, SUBX, MULX, SET, ADD, SUB, MUL, JMP, JZ, JNZ];
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OP {
+    NOOP,
+    Addx(i32),
+    Subx(i32),
+    Mulx(i32),
+    Set(Register, Operand),
+    Add(Register, Operand),
+    Sub(Register, Operand),
+    Mul(Register, Operand),
+    /// Jumps are relative, `jmp -2` goes back two instructions.
+    Jmp(i32),
+    Jz(Register, i32),
+    Jnz(Register, i32),
+}
+
+impl OP {
+    /// The instruction's row in `INSTRUCTIONS`.
+    pub fn instruction(&self) -> &'static Instruction {
+        match self {
+            OP::NOOP => &NOOP,
+            OP::Addx(_) => &ADDX,
+            OP::Subx(_) => &SUBX,
+            OP::Mulx(_) => &MULX,
+            OP::Set(..) => &SET,
+            OP::Add(..) => &ADD,
+            OP::Sub(..) => &SUB,
+            OP::Mul(..) => &MUL,
+            OP::Jmp(_) => &JMP,
+            OP::Jz(..) => &JZ,
+            OP::Jnz(..) => &JNZ,
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        self.instruction().mnemonic
+    }
+
+    /// The operands in the order `build` takes them.
+    pub fn operands(&self) -> Vec<Operand> {
+        match *self {
+            OP::NOOP => vec![],
+            OP::Addx(value) | OP::Subx(value) | OP::Mulx(value) | OP::Jmp(value) => {
+                vec![Operand::Value(value)]
+            }
+            OP::Set(register, source)
+            | OP::Add(register, source)
+            | OP::Sub(register, source)
+            | OP::Mul(register, source) => vec![Operand::Register(register), source],
+            OP::Jz(register, offset) | OP::Jnz(register, offset) => {
+                vec![Operand::Register(register), Operand::Value(offset)]
+            }
+        }
+    }
+
+    /// Cycles the instruction takes to complete.
+    pub fn cycles(&self) -> u32 {
+        self.instruction().cycles
+    }
+}
+
+impl FromStr for OP {
+    type Err = ScanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mnemonic, _) = s.split_once(' ').unwrap_or((s, ""));
+        let Some(instruction) = INSTRUCTIONS.iter().find(|i| i.mnemonic == mnemonic) else {
+            return Err(ScanError::new(
+                1,
+                format!("unknown instruction `{}`", mnemonic),
+            ));
+        };
+
+        let operands = match instruction.operands.len() {
+            0 => common::scan(mnemonic, s).map(|()| vec![])?,
+            1 => common::scan(&format!("{} {{}}", mnemonic), s).map(|(a,)| vec![a])?,
+            _ => common::scan(&format!("{} {{}} {{}}", mnemonic), s).map(|(a, b)| vec![a, b])?,
+        };
+
+        let mut column = mnemonic.len() + 2;
+        for ((kind, operand), text) in instruction
+            .operands
+            .iter()
+            .zip(&operands)
+            .zip(s.split(' ').skip(1))
+        {
+            if !kind.accepts(operand) {
+                return Err(ScanError::new(
+                    column,
+                    format!("`{}` takes {} here", mnemonic, kind),
+                ));
+            }
+            column += text.chars().count() + 1;
+        }
+
+        Ok((instruction.build)(&operands))
+    }
+}
+
+/// Writes the instruction as it is in the input, so it parses back to the same `OP`. Width
+/// and alignment apply to the whole instruction.
+impl fmt::Display for OP {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut text = self.mnemonic().to_string();
+        for operand in self.operands() {
+            text = format!("{} {}", text, operand);
+        }
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::{Operand, Register, INSTRUCTIONS, OP};
+
+    #[test]
+    fn parses_every_instruction() {
+        let program = [
+            ("noop", OP::NOOP),
+            ("addx -3", OP::Addx(-3)),
+            ("subx 4", OP::Subx(4)),
+            ("mulx 2", OP::Mulx(2)),
+            ("set y 7", OP::Set(Register::Y, Operand::Value(7))),
+            (
+                "add x z",
+                OP::Add(Register::X, Operand::Register(Register::Z)),
+            ),
+            ("sub z 1", OP::Sub(Register::Z, Operand::Value(1))),
+            (
+                "mul y y",
+                OP::Mul(Register::Y, Operand::Register(Register::Y)),
+            ),
+            ("jmp -2", OP::Jmp(-2)),
+            ("jz y 3", OP::Jz(Register::Y, 3)),
+            ("jnz x -1", OP::Jnz(Register::X, -1)),
+        ];
+
+        assert_eq!(INSTRUCTIONS.len(), program.len());
+        for (idx, (text, op)) in program.into_iter().enumerate() {
+            assert_eq!(op, text.parse().unwrap());
+            assert_eq!(text, op.to_string());
+
+            // Each variant maps to its own row, whose operand kinds and `build` agree with it
+            let instruction = op.instruction();
+            assert_eq!(INSTRUCTIONS[idx].mnemonic, instruction.mnemonic);
+            let operands = op.operands();
+            assert_eq!(instruction.operands.len(), operands.len());
+            assert!(instruction
+                .operands
+                .iter()
+                .zip(&operands)
+                .all(|(kind, operand)| kind.accepts(operand)));
+            assert_eq!(op, (instruction.build)(&operands));
+        }
+        assert_eq!("jz y 3  |", format!("{:<8}|", OP::Jz(Register::Y, 3)));
+    }
+
+    #[test]
+    fn checks_operand_kinds() {
+        let error = |text: &str| text.parse::<OP>().unwrap_err().to_string();
+
+        assert_eq!("column 6: `addx` takes a number here", error("addx y"));
+        assert_eq!(
+            "column 5: `set` takes a register (x, y or z) here",
+            error("set 4 y")
+        );
+        assert_eq!("column 7: `jnz` takes an offset here", error("jnz y x"));
+        assert_eq!(
+            "column 5: could not parse `1 2`: invalid digit found in string",
+            error("jmp 1 2")
+        );
+    }
+}
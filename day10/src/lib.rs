@@ -0,0 +1,289 @@
+use common::{Answer, Error, Grid, Solver};
+
+mod assembler;
+pub mod debugger;
+mod instructions;
+pub mod listing;
+pub mod trace;
+
+pub use instructions::{Instruction, Operand, OperandKind, Register, INSTRUCTIONS, OP};
+
+struct Program {
+    pc: i32,
+    cycle_pc: i32,
+    x_reg: i32,
+    y_reg: i32,
+    z_reg: i32,
+    signal_strength: i32,
+    pixel_buffer: Grid<char>,
+    /// Index of the instruction in progress, or the next one to start.
+    ip: usize,
+    /// Cycles of that instruction already run.
+    op_cycle: u32,
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program {
+            pc: 1,
+            cycle_pc: 0,
+            x_reg: 1,
+            y_reg: 0,
+            z_reg: 0,
+            signal_strength: 0,
+            pixel_buffer: Grid::new(40, 6, '.'),
+            ip: 0,
+            op_cycle: 0,
+        }
+    }
+
+    /// Runs until the program ends or the screen has been drawn, so programs that loop
+    /// forever still finish.
+    pub fn run(&mut self, ops: &[OP]) {
+        while !self.screen_drawn() && self.tick(ops).is_some() {}
+    }
+
+    /// Runs one cycle and returns the instruction it belonged to with its index, or `None`
+    /// once the program has ended. The instruction is complete when `op_cycle` is back to 0.
+    fn tick(&mut self, ops: &[OP]) -> Option<(usize, OP)> {
+        let (ip, op) = (self.ip, *ops.get(self.ip)?);
+
+        self.draw_pixel();
+        self.op_cycle += 1;
+        if self.op_cycle == op.cycles() {
+            self.op_cycle = 0;
+            self.complete(op);
+        }
+        self.increment_pc();
+
+        Some((ip, op))
+    }
+
+    /// Applies the instruction at the end of its last cycle and moves to the next one.
+    fn complete(&mut self, op: OP) {
+        let offset = match op {
+            OP::NOOP => 1,
+            OP::Addx(value) => self.apply(Register::X, Operand::Value(value), i32::wrapping_add),
+            OP::Subx(value) => self.apply(Register::X, Operand::Value(value), i32::wrapping_sub),
+            OP::Mulx(value) => self.apply(Register::X, Operand::Value(value), i32::wrapping_mul),
+            OP::Set(register, source) => self.apply(register, source, |_, value| value),
+            OP::Add(register, source) => self.apply(register, source, i32::wrapping_add),
+            OP::Sub(register, source) => self.apply(register, source, i32::wrapping_sub),
+            OP::Mul(register, source) => self.apply(register, source, i32::wrapping_mul),
+            OP::Jmp(offset) => offset,
+            OP::Jz(register, offset) if self.read(register) == 0 => offset,
+            OP::Jnz(register, offset) if self.read(register) != 0 => offset,
+            OP::Jz(..) | OP::Jnz(..) => 1,
+        };
+
+        // Jumping before the first instruction ends the program, like jumping past the last
+        self.ip = self
+            .ip
+            .checked_add_signed(offset as isize)
+            .unwrap_or(usize::MAX);
+    }
+
+    /// Sets `register` to `f(register, source)`, and returns the offset to the next instruction.
+    fn apply(&mut self, register: Register, source: Operand, f: fn(i32, i32) -> i32) -> i32 {
+        let value = match source {
+            Operand::Value(value) => value,
+            Operand::Register(source) => self.read(source),
+        };
+        let register = self.register_mut(register);
+        *register = f(*register, value);
+        1
+    }
+
+    fn read(&self, register: Register) -> i32 {
+        match register {
+            Register::X => self.x_reg,
+            Register::Y => self.y_reg,
+            Register::Z => self.z_reg,
+        }
+    }
+
+    fn register_mut(&mut self, register: Register) -> &mut i32 {
+        match register {
+            Register::X => &mut self.x_reg,
+            Register::Y => &mut self.y_reg,
+            Register::Z => &mut self.z_reg,
+        }
+    }
+
+    fn finished(&self, ops: &[OP]) -> bool {
+        self.ip >= ops.len()
+    }
+
+    fn screen_drawn(&self) -> bool {
+        self.pc as usize > self.pixel_buffer.width() * self.pixel_buffer.height()
+    }
+
+    fn increment_pc(&mut self) {
+        self.pc += 1;
+        self.cycle_pc += 1;
+
+        if self.cycle_pc >= 40 {
+            self.cycle_pc = 0;
+        }
+
+        self.calc_signal_strength();
+    }
+
+    fn calc_signal_strength(&mut self) {
+        match self.pc {
+            20 | 60 | 100 | 140 | 180 | 220 => {
+                // Wraps like the registers do
+                let strength = self.pc.wrapping_mul(self.x_reg);
+                self.signal_strength = self.signal_strength.wrapping_add(strength);
+            }
+            _ => (),
+        }
+    }
+
+    fn draw_pixel(&mut self) {
+        let row = (self.pc - 1) as usize / self.pixel_buffer.width();
+
+        if self.cycle_pc >= self.x_reg - 1 && self.cycle_pc <= self.x_reg + 1 {
+            if let Some(pixel) = self.pixel_buffer.get_mut(self.cycle_pc as usize, row) {
+                *pixel = '#';
+            }
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<OP>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        assembler::assemble(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).to_string().into()
+    }
+
+    fn validate(input: &str) -> Vec<Error> {
+        assembler::assemble_all(input).err().unwrap_or_default()
+    }
+}
+
+fn part_one(input: &[OP]) -> i32 {
+    let mut program = Program::new();
+    program.run(input);
+    program.signal_strength
+}
+
+fn part_two(input: &[OP]) -> Grid<char> {
+    let mut program = Program::new();
+    program.run(input);
+    program.pixel_buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Day10, Program};
+    use common::Solver;
+
+    fn run(program: &str) -> Program {
+        let mut cpu = Program::new();
+        cpu.run(&Day10::parse(program).unwrap());
+        cpu
+    }
+
+    #[test]
+    fn example_answers() {
+        let image = [
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ];
+
+        common::testing::assert_example::<Day10>(13140, image.join("\n"));
+    }
+
+    #[test]
+    fn malformed_instructions_are_reported() {
+        let unknown = Day10::parse("noop\ndivx 3").err().unwrap();
+        let bad_value = Day10::parse("addx 1\naddx one").err().unwrap();
+
+        assert_eq!(
+            "line 2, column 1: unknown instruction `divx` (`divx 3`)",
+            unknown.to_string()
+        );
+        assert_eq!(
+            "line 2, column 6: unknown constant or label `one` (`addx one`)",
+            bad_value.to_string()
+        );
+    }
+
+    #[test]
+    fn validate_lists_every_bad_instruction() {
+        let errors = Day10::validate("noop\ndivx 3\naddx 2\naddx\nnoop 1\n");
+
+        assert_eq!(3, errors.len());
+        assert!(errors[0].to_string().starts_with("line 2, column 1"));
+        assert!(errors[1].to_string().starts_with("line 4"));
+        assert!(errors[2].to_string().starts_with("line 5"));
+    }
+
+    #[test]
+    fn validate_accepts_assembler_programs() {
+        let program = "
+; counts y down
+.const COUNT 3
+.macro step by
+    addx by
+.endm
+    set y COUNT
+again:
+    step -1     ; and back up
+    step 1
+    sub y 1
+    jnz y again
+";
+        assert!(Day10::validate(program).is_empty());
+        assert!(Day10::validate("start:\n  addx 1\n  jmp start\n").is_empty());
+    }
+
+    #[test]
+    fn runs_loops_and_registers() {
+        let cpu = run("set y 3\naddx 2\nsub y 1\njnz y -2\n");
+        assert_eq!((7, 0), (cpu.x_reg, cpu.y_reg));
+        assert_eq!(1 + 3 * 6, cpu.pc - 1);
+
+        let cpu = run("mulx 3\njz x 3\nsubx 1\njmp 2\naddx 100\nset z x\n");
+        assert_eq!((2, 2), (cpu.x_reg, cpu.z_reg));
+        assert_eq!(3 + 2 + 2 + 1 + 1, cpu.pc - 1);
+    }
+
+    #[test]
+    fn endless_programs_stop_once_the_screen_is_drawn() {
+        let cpu = run("addx 1\njmp -1\n");
+
+        assert_eq!(241, cpu.pc);
+        // Jumping before the first instruction ends the program
+        assert_eq!(1, run("jmp -5\naddx 1\n").x_reg);
+    }
+
+    #[test]
+    fn signal_strength_wraps_with_the_registers() {
+        let program = format!("mulx 100000\nmulx 100000\nmulx 3\n{}", "noop\n".repeat(240));
+        let ops = Day10::parse(&program).unwrap();
+
+        let x = 100_000i32.wrapping_mul(100_000).wrapping_mul(3);
+        let expected = [20i32, 60, 100, 140, 180, 220]
+            .iter()
+            .fold(0i32, |sum, pc| sum.wrapping_add(pc.wrapping_mul(x)));
+        assert_eq!(common::Answer::from(expected), Day10::part_one(&ops));
+    }
+}
@@ -0,0 +1,156 @@
+//! Annotated program listing, written with `day10 --list [input]`. Each instruction is printed
+//! as it parses, followed by a comment with when it first ran and what X was after it, so a
+//! listing is itself a valid program.
+
+use crate::{Day10, Program, OP};
+use common::cli;
+use common::{Args, Error, Solver};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// One instruction of the program, numbered from 1 like `pc` in the trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub pc: usize,
+    pub op: OP,
+    /// Cycle the first run started on, `None` if it never ran.
+    pub cycle: Option<i32>,
+    /// X after the first run, `None` if it never completed.
+    pub x_after: Option<i32>,
+    pub runs: usize,
+}
+
+/// Runs the program like `part_one` does, recording the first run of each instruction.
+pub fn listing(ops: &[OP]) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = ops
+        .iter()
+        .enumerate()
+        .map(|(idx, op)| Entry {
+            pc: idx + 1,
+            op: *op,
+            cycle: None,
+            x_after: None,
+            runs: 0,
+        })
+        .collect();
+    let mut program = Program::new();
+
+    while !program.screen_drawn() {
+        let (cycle, starting) = (program.pc, program.op_cycle == 0);
+        let Some((idx, _)) = program.tick(ops) else {
+            break;
+        };
+
+        let entry = &mut entries[idx];
+        if starting {
+            entry.runs += 1;
+            entry.cycle.get_or_insert(cycle);
+        }
+        if program.op_cycle == 0 {
+            entry.x_after.get_or_insert(program.x_reg);
+        }
+    }
+    entries
+}
+
+pub fn to_text(entries: &[Entry]) -> String {
+    let width = entries
+        .iter()
+        .map(|entry| entry.op.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("; instruction".len());
+    let number = |value: Option<i32>| value.map_or("-".to_string(), |v| v.to_string());
+
+    let mut out = format!(
+        "{:<width$} ; {:>4} {:>6} {:>5} {:>8}\n",
+        "; instruction", "pc", "cycle", "cost", "x after"
+    );
+    for entry in entries {
+        let _ = write!(
+            out,
+            "{:<width$} ; {:>4} {:>6} {:>5} {:>8}",
+            entry.op,
+            entry.pc,
+            number(entry.cycle),
+            entry.op.cycles(),
+            number(entry.x_after)
+        );
+        let _ = match entry.runs {
+            0 | 1 => writeln!(out),
+            runs => writeln!(out, "  (first of {} runs)", runs),
+        };
+    }
+    out
+}
+
+/// `day10 --list [options] [input]`.
+pub fn main(args: Vec<String>) {
+    if let Err(error) = try_main(args) {
+        common::exit_with(&error);
+    }
+}
+
+fn try_main(args: Vec<String>) -> Result<(), Error> {
+    let args = Args::from_args(args.into_iter().filter(|arg| arg != "--list"))?;
+    if args.help {
+        println!(
+            "usage: day10 --list [options] [input]\n\n{}",
+            cli::OPTIONS_HELP
+        );
+        return Ok(());
+    }
+    let ops = Day10::parse(&args.load_input(Day10::DAY)?)?;
+    let out = to_text(&listing(&ops));
+
+    match io::stdout().lock().write_all(out.as_bytes()) {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => {
+            Err(Error::write("stdout", error))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::listing::{listing, to_text};
+    use crate::{Day10, OP};
+    use common::Solver;
+
+    #[test]
+    fn annotates_each_instruction() {
+        let ops = Day10::parse("noop\naddx 3\naddx -5\n").unwrap();
+
+        assert_eq!(
+            "; instruction ;   pc  cycle  cost  x after
+noop          ;    1      1     1        1
+addx 3        ;    2      2     2        4
+addx -5       ;    3      4     2       -1
+",
+            to_text(&listing(&ops))
+        );
+    }
+
+    #[test]
+    fn loops_show_their_first_run() {
+        let ops = Day10::parse("set y 2\naddx 1\nsub y 1\njnz y -2\njmp 0\nnoop\n").unwrap();
+        let text = to_text(&listing(&ops));
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(
+            "addx 1        ;    2      2     2        2  (first of 2 runs)",
+            lines[2]
+        );
+        assert_eq!("noop          ;    6      -     1        -", lines[6]);
+    }
+
+    #[test]
+    fn listings_assemble_back_to_the_program() {
+        let ops = Day10::parse(&common::testing::example(10)).unwrap();
+        let text = to_text(&listing(&ops));
+        let listed: Vec<OP> = Day10::parse(&text).unwrap();
+
+        assert_eq!(ops, listed);
+        assert!(Day10::validate(&text).is_empty());
+    }
+}
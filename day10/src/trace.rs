@@ -0,0 +1,181 @@
+//! Cycle by cycle record of a CPU run, written with `day10 --trace csv|jsonl [input]` for
+//! plotting or diffing against another implementation.
+
+use crate::{Day10, Program, OP};
+use common::cli;
+use common::report::json_string;
+use common::{Args, Error, Solver};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    Csv,
+    JsonLines,
+}
+
+impl TraceFormat {
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "csv" => Ok(TraceFormat::Csv),
+            "jsonl" => Ok(TraceFormat::JsonLines),
+            _ => Err(Error::argument(format!(
+                "invalid trace format `{}`, expected csv or jsonl",
+                value
+            ))),
+        }
+    }
+}
+
+/// State of one cycle, cycles and instructions (`pc`) are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub cycle: i32,
+    pub pc: usize,
+    pub op: OP,
+    pub x_during: i32,
+    pub x_after: i32,
+    pub column: i32,
+    pub row: usize,
+    pub pixel: char,
+}
+
+/// Runs the program one cycle at a time, stopping where `part_one` and `part_two` do.
+pub fn trace(ops: &[OP]) -> Vec<Cycle> {
+    let mut program = Program::new();
+    let mut cycles = vec![];
+
+    while !program.screen_drawn() {
+        let (cycle, column, x_during) = (program.pc, program.cycle_pc, program.x_reg);
+        let Some((idx, op)) = program.tick(ops) else {
+            break;
+        };
+
+        let row = (cycle - 1) as usize / program.pixel_buffer.width();
+        cycles.push(Cycle {
+            cycle,
+            pc: idx + 1,
+            op,
+            x_during,
+            x_after: program.x_reg,
+            column,
+            row,
+            pixel: *program
+                .pixel_buffer
+                .get(column as usize, row)
+                .expect("tracing stops once the screen is drawn"),
+        });
+    }
+    cycles
+}
+
+pub fn to_csv(cycles: &[Cycle]) -> String {
+    let mut out = String::from("cycle,pc,instruction,x_during,x_after,column,row,pixel\n");
+    for c in cycles {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            c.cycle, c.pc, c.op, c.x_during, c.x_after, c.column, c.row, c.pixel
+        );
+    }
+    out
+}
+
+/// One JSON object per line.
+pub fn to_json_lines(cycles: &[Cycle]) -> String {
+    let mut out = String::new();
+    for c in cycles {
+        let _ = writeln!(
+            out,
+            "{{\"cycle\":{},\"pc\":{},\"instruction\":{},\"x_during\":{},\"x_after\":{},\"column\":{},\"row\":{},\"pixel\":{}}}",
+            c.cycle,
+            c.pc,
+            json_string(&c.op.to_string()),
+            c.x_during,
+            c.x_after,
+            c.column,
+            c.row,
+            json_string(&c.pixel.to_string())
+        );
+    }
+    out
+}
+
+/// `day10 --trace <format> [options] [input]`.
+pub fn main(args: Vec<String>) {
+    if let Err(error) = try_main(args) {
+        common::exit_with(&error);
+    }
+}
+
+fn try_main(mut args: Vec<String>) -> Result<(), Error> {
+    // Checked first, as the format would otherwise be taken from whatever follows `--trace`
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!(
+            "usage: day10 --trace <csv|jsonl> [options] [input]\n\n{}",
+            cli::OPTIONS_HELP
+        );
+        return Ok(());
+    }
+
+    let idx = args.iter().position(|arg| arg == "--trace").unwrap();
+    if idx + 1 >= args.len() {
+        return Err(Error::argument("--trace needs a format, csv or jsonl"));
+    }
+    let format = TraceFormat::parse(&args.remove(idx + 1))?;
+    args.remove(idx);
+
+    let args = Args::from_args(args)?;
+    let ops = Day10::parse(&args.load_input(Day10::DAY)?)?;
+    let cycles = trace(&ops);
+
+    let out = match format {
+        TraceFormat::Csv => to_csv(&cycles),
+        TraceFormat::JsonLines => to_json_lines(&cycles),
+    };
+    // Traces are often piped into `head`, which closing early is not an error
+    match io::stdout().lock().write_all(out.as_bytes()) {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => {
+            Err(Error::write("stdout", error))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::{to_csv, to_json_lines, trace};
+    use crate::{part_two, Day10};
+    use common::Solver;
+
+    #[test]
+    fn traces_every_cycle() {
+        let ops = Day10::parse(&common::testing::example(10)).unwrap();
+        let cycles = trace(&ops);
+
+        assert_eq!(240, cycles.len());
+        assert_eq!(21, cycles[19].x_during);
+        assert_eq!((11, 19), (cycles[19].pc, cycles[19].column));
+
+        let drawn: String = cycles.iter().map(|c| c.pixel).collect();
+        assert_eq!(part_two(&ops).to_string().replace('\n', ""), drawn);
+    }
+
+    #[test]
+    fn exports_csv_and_json_lines() {
+        let cycles = trace(&Day10::parse("noop\naddx 3\n").unwrap());
+
+        assert_eq!(
+            "cycle,pc,instruction,x_during,x_after,column,row,pixel
+1,1,noop,1,1,0,0,#
+2,2,addx 3,1,1,1,0,#
+3,2,addx 3,1,4,2,0,#
+",
+            to_csv(&cycles)
+        );
+        assert_eq!(
+            r##"{"cycle":3,"pc":2,"instruction":"addx 3","x_during":1,"x_after":4,"column":2,"row":0,"pixel":"#"}"##,
+            to_json_lines(&cycles).lines().last().unwrap()
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use common::{Answer, Block, Error, List, Solver};
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let blocks: Vec<Block> = common::blocks(input).collect();
+        let monkeys = blocks
+            .iter()
+            .map(Monkey::parse)
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for (idx, (block, monkey)) in blocks.iter().zip(&monkeys).enumerate() {
+            if let Some(error) = monkey.check(idx, block, monkeys.len()).into_iter().next() {
+                return Err(error);
+            }
+        }
+        if monkeys.len() < 2 {
+            return Err(too_few_monkeys(input));
+        }
+        Ok(monkeys)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+
+    fn validate(input: &str) -> Vec<Error> {
+        let blocks: Vec<Block> = common::blocks(input).collect();
+        let mut errors = vec![];
+
+        for (idx, block) in blocks.iter().enumerate() {
+            let monkey = match Monkey::parse(block) {
+                Ok(monkey) => monkey,
+                Err(error) => {
+                    errors.push(error);
+                    continue;
+                }
+            };
+            if block.lines[0] != format!("Monkey {}:", idx) {
+                let header = block.numbered().next().unwrap();
+                errors.push(header.error(format!("expected `Monkey {}:`", idx)));
+            }
+            errors.extend(monkey.check(idx, block, blocks.len()));
+        }
+
+        if blocks.len() < 2 {
+            errors.push(too_few_monkeys(input));
+        }
+        errors
+    }
+}
+
+fn too_few_monkeys(input: &str) -> Error {
+    let last = input.lines().count().max(1);
+    Error::parse(last, "", "at least two monkeys are needed")
+}
+
+fn part_one(input: &[Monkey]) -> u64 {
+    let mut monkeys: Vec<Monkey> = input
+        .iter()
+        .map(|monkey| Monkey {
+            worry_div_by: 3,
+            ..monkey.clone()
+        })
+        .collect();
+
+    for _ in 0..20 {
+        for i in 0..monkeys.len() {
+            while !monkeys[i].items.is_empty() {
+                let inspected_item = &monkeys[i].inspect(0);
+                let target = &monkeys[i].get_throw_target(inspected_item);
+                monkeys[*target].items.push_back(*inspected_item);
+            }
+        }
+    }
+
+    monkeys.sort_by_key(|m| Reverse(m.inspected));
+    monkeys[0].inspected * monkeys[1].inspected
+}
+
+fn part_two(input: &[Monkey]) -> u64 {
+    let mut monkeys: Vec<Monkey> = input.to_vec();
+
+    let modulo: u64 = monkeys.iter().map(|m| m.target_cond).product();
+    for _ in 0..10_000 {
+        for i in 0..monkeys.len() {
+            while !monkeys[i].items.is_empty() {
+                let inspected_item = &monkeys[i].inspect(modulo);
+                let target = &monkeys[i].get_throw_target(inspected_item);
+                monkeys[*target].items.push_back(*inspected_item);
+            }
+        }
+    }
+
+    monkeys.sort_by_key(|m| Reverse(m.inspected));
+    monkeys[0].inspected * monkeys[1].inspected
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Operation {
+    Add(u64),
+    Sub(u64),
+    Multi(u64),
+    MultiSelf,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Operand {
+    Old,
+    Value(u64),
+}
+
+impl FromStr for Operand {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "old" => Ok(Operand::Old),
+            _ => s.parse().map(Operand::Value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Monkey {
+    inspected: u64,
+    items: VecDeque<u64>,
+    op: Operation,
+    target_cond: u64,
+    target_true: usize,
+    target_false: usize,
+    worry_div_by: usize,
+}
+
+impl Monkey {
+    pub fn parse(monkey_descriptor: &Block) -> Result<Self, Error> {
+        let mut lines = monkey_descriptor.numbered();
+        let mut next_line = |expected: &str| {
+            lines
+                .next()
+                .ok_or_else(|| monkey_descriptor.ended_early(expected))
+        };
+
+        // The header is only checked, monkeys are numbered by their position
+        let (_,): (String,) = next_line("monkey header")?.scan("Monkey {}:")?;
+
+        let (List(items),): (List<u64>,) =
+            next_line("starting items")?.scan("  Starting items: {}")?;
+
+        let op_line = next_line("operation")?;
+        let (operator, operand): (char, Operand) = op_line.scan("  Operation: new = old {} {}")?;
+        let op = match (operator, operand) {
+            ('+', Operand::Value(n)) => Operation::Add(n),
+            ('-', Operand::Value(n)) => Operation::Sub(n),
+            ('*', Operand::Value(n)) => Operation::Multi(n),
+            ('*', Operand::Old) => Operation::MultiSelf,
+            _ => return Err(op_line.error("unsupported operation")),
+        };
+
+        let (target_cond,): (u64,) = next_line("test")?.scan("  Test: divisible by {}")?;
+
+        let (target_true,): (usize,) =
+            next_line("true target")?.scan("    If true: throw to monkey {}")?;
+        let (target_false,): (usize,) =
+            next_line("false target")?.scan("    If false: throw to monkey {}")?;
+
+        if let Some(line) = lines.next() {
+            return Err(line.error("unexpected line after monkey description"));
+        }
+
+        Ok(Monkey {
+            inspected: 0,
+            items: items.into(),
+            op,
+            target_cond,
+            target_true,
+            target_false,
+            worry_div_by: 1,
+        })
+    }
+
+    /// What solving relies on, for monkey `idx` of `count`: a divisor to test with, and
+    /// throws to another monkey that exists.
+    fn check(&self, idx: usize, block: &Block, count: usize) -> Vec<Error> {
+        let line = |offset| block.numbered().nth(offset).unwrap();
+        let mut errors = vec![];
+
+        if self.target_cond == 0 {
+            errors.push(line(3).error("cannot test for divisibility by 0"));
+        }
+        for (offset, target) in [(4, self.target_true), (5, self.target_false)] {
+            if target == idx {
+                errors.push(line(offset).error("a monkey cannot throw to itself"));
+            } else if target >= count {
+                errors.push(line(offset).error(format!(
+                    "there is no monkey {}, the last is {}",
+                    target,
+                    count - 1
+                )));
+            }
+        }
+        errors
+    }
+
+    fn inspect(&mut self, modulo: u64) -> u64 {
+        let item = self.items.pop_front().unwrap();
+        let new_item = match self.op {
+            Operation::Add(n) => item + n,
+            // Worry levels cannot drop below zero
+            Operation::Sub(n) => item.saturating_sub(n),
+            Operation::Multi(n) => item * n,
+            Operation::MultiSelf => item * item,
+        };
+
+        self.inspected += 1;
+
+        if self.worry_div_by == 1 {
+            new_item % modulo
+        } else {
+            new_item / self.worry_div_by as u64
+        }
+    }
+
+    fn get_throw_target(&self, item: &u64) -> usize {
+        if item.is_multiple_of(self.target_cond) {
+            self.target_true
+        } else {
+            self.target_false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Day11;
+    use common::Solver;
+
+    #[test]
+    fn example_answers() {
+        common::testing::assert_example::<Day11>(10605, 2713310158u64);
+    }
+
+    #[test]
+    fn malformed_monkeys_are_reported() {
+        let input = "Monkey 0:
+  Starting items: 79, x8
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+";
+        let error = Day11::parse(input).err().unwrap();
+
+        assert_eq!(
+            "line 2, column 23: could not parse `x8`: invalid digit found in string (`  Starting items: 79, x8`)",
+            error.to_string()
+        );
+        assert!(Day11::parse("Monkey 0:\n  Starting items: 79").is_err());
+        assert_eq!(
+            "line 1, column 9: expected line to end with `:` (`Monkey 0`)",
+            Day11::parse(&input.replace("0:", "0"))
+                .err()
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn monkeys_that_cannot_be_solved_are_rejected() {
+        let example = common::testing::example(11);
+        let error = |input: &str| Day11::parse(input).err().unwrap().to_string();
+
+        let first = &example[..example.find("\n\n").unwrap()];
+        assert_eq!(
+            "line 5: there is no monkey 2, the last is 0 (`    If true: throw to monkey 2`)",
+            error(first)
+        );
+        assert_eq!(
+            "line 4: cannot test for divisibility by 0 (`  Test: divisible by 0`)",
+            error(&example.replacen("divisible by 23", "divisible by 0", 1))
+        );
+        assert_eq!(
+            "line 5: a monkey cannot throw to itself (`    If true: throw to monkey 0`)",
+            error(&example.replacen("throw to monkey 2", "throw to monkey 0", 1))
+        );
+    }
+
+    #[test]
+    fn worry_levels_stop_at_zero() {
+        let input = common::testing::example(11).replace("old + 6", "old - 100");
+        let monkeys = Day11::parse(&input).unwrap();
+        assert!(crate::part_one(&monkeys) > 0);
+        assert!(crate::part_two(&monkeys) > 0);
+    }
+
+    #[test]
+    fn validate_lists_every_bad_monkey() {
+        let input = "Monkey 0:
+  Starting items: 79
+  Operation: new = old * 19
+  Test: divisible by 0
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+
+Monkey 2:
+  Starting items: 54, x
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+
+Monkey 2:
+  Starting items: 54
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 5
+";
+        let errors: Vec<String> = Day11::validate(input)
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(4, errors.len(), "{:?}", errors);
+        assert!(
+            errors[0].starts_with("line 4: cannot test"),
+            "{}",
+            errors[0]
+        );
+        assert!(
+            errors[1].starts_with("line 5: a monkey cannot"),
+            "{}",
+            errors[1]
+        );
+        assert!(errors[2].starts_with("line 9, column 23"), "{}", errors[2]);
+        assert!(errors[3].starts_with("line 20: there is no monkey 5"));
+
+        assert!(Day11::validate(&common::testing::example(11)).is_empty());
+        let alone = Day11::validate(&input[..input.find("\n\n").unwrap()]);
+        assert_eq!(
+            "line 6: at least two monkeys are needed (``)",
+            alone.last().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use common::{Answer, Error, Line, ReadLines, Solver};
+use std::str::FromStr;
+
+// A | X is rock
+// B | Y is paper
+// C | Z is scissors
+
+// X is lose
+// Y is draw
+// Z is win
+
+// Score per shape, Rock = 1, Paper = 2, Scissors = 3
+// Score per game, Lost = 0, Draw = 3, Win = 6
+
+#[derive(Copy, Clone)]
+enum RPSShape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl RPSShape {
+    fn score(&self) -> i32 {
+        match self {
+            RPSShape::Rock => 1,
+            RPSShape::Paper => 2,
+            RPSShape::Scissors => 3,
+        }
+    }
+
+    fn to_lose(self) -> RPSShape {
+        match self {
+            RPSShape::Rock => RPSShape::Scissors,
+            RPSShape::Paper => RPSShape::Rock,
+            RPSShape::Scissors => RPSShape::Paper,
+        }
+    }
+
+    fn to_win(self) -> RPSShape {
+        match self {
+            RPSShape::Rock => RPSShape::Paper,
+            RPSShape::Paper => RPSShape::Scissors,
+            RPSShape::Scissors => RPSShape::Rock,
+        }
+    }
+
+    fn to_draw(self) -> RPSShape {
+        self
+    }
+
+    fn outcome_required(&self, result_needed: Response) -> RPSShape {
+        match result_needed {
+            Response::X => self.to_lose(),
+            Response::Y => self.to_draw(),
+            Response::Z => self.to_win(),
+        }
+    }
+
+    fn versus(&self, other: &RPSShape) -> i32 {
+        match (self, other) {
+            (RPSShape::Rock, RPSShape::Scissors) => 6,
+            (RPSShape::Scissors, RPSShape::Rock) => 0,
+            (_, _) if self.score() > other.score() => 6,
+            (_, _) if self.score() == other.score() => 3,
+            (_, _) if self.score() < other.score() => 0,
+            _ => 0, // realistically this would be an error thrown and handled?
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ShapeScoreError;
+impl FromStr for RPSShape {
+    type Err = ShapeScoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(RPSShape::Rock),
+            "B" => Ok(RPSShape::Paper),
+            "C" => Ok(RPSShape::Scissors),
+            _ => Err(ShapeScoreError),
+        }
+    }
+}
+
+/// The second column, a shape to throw in part one and the outcome needed in part two.
+#[derive(Copy, Clone)]
+enum Response {
+    X,
+    Y,
+    Z,
+}
+
+impl Response {
+    fn shape(self) -> RPSShape {
+        match self {
+            Response::X => RPSShape::Rock,
+            Response::Y => RPSShape::Paper,
+            Response::Z => RPSShape::Scissors,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Round {
+    opponent: RPSShape,
+    response: Response,
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Round>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        common::numbered_lines(input)
+            .map(|line| parse_round(&line))
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+
+    fn stream(lines: ReadLines) -> Result<[Answer; 2], Error> {
+        let (mut total_score, mut rigged_score) = (0, 0);
+        for line in lines {
+            let round = parse_round(&line?.line())?;
+            total_score += round_score(round);
+            rigged_score += rigged_round_score(round);
+        }
+
+        Ok([total_score.into(), rigged_score.into()])
+    }
+
+    fn validate(input: &str) -> Vec<Error> {
+        common::numbered_lines(input)
+            .filter_map(|line| parse_round(&line).err())
+            .collect()
+    }
+}
+
+fn part_one(input: &[Round]) -> i32 {
+    input.iter().map(|round| round_score(*round)).sum()
+}
+
+fn part_two(input: &[Round]) -> i32 {
+    input.iter().map(|round| rigged_round_score(*round)).sum()
+}
+
+fn round_score(round: Round) -> i32 {
+    determine_round_score(&round.opponent, &round.response.shape())
+}
+
+fn rigged_round_score(round: Round) -> i32 {
+    determine_round_score(
+        &round.opponent,
+        &round.opponent.outcome_required(round.response),
+    )
+}
+
+/// A round must be exactly an opponent shape `A`, `B` or `C`, a space, then `X`, `Y` or `Z`.
+fn parse_round(line: &Line) -> Result<Round, Error> {
+    let expected = [("A, B or C", "ABC"), ("a space", " "), ("X, Y or Z", "XYZ")];
+    let mut chars = line.text.chars();
+
+    for (idx, (name, allowed)) in expected.iter().enumerate() {
+        match chars.next() {
+            Some(c) if allowed.contains(c) => {}
+            Some(c) => {
+                return Err(line.error_at(idx + 1, format!("expected {}, found `{}`", name, c)))
+            }
+            None => return Err(line.error_at(idx + 1, format!("expected {}", name))),
+        }
+    }
+    if chars.next().is_some() {
+        return Err(line.error_at(4, "unexpected text after the round"));
+    }
+
+    let (opponent, response) = line.text.split_at(2);
+    Ok(Round {
+        opponent: opponent.trim_end().parse().expect("checked above"),
+        response: match response {
+            "X" => Response::X,
+            "Y" => Response::Y,
+            "Z" => Response::Z,
+            _ => unreachable!("checked above"),
+        },
+    })
+}
+
+fn determine_round_score(opponent_shape: &RPSShape, player_shape: &RPSShape) -> i32 {
+    let mut player_score = 0;
+
+    player_score += player_shape.score();
+    player_score += player_shape.versus(opponent_shape);
+
+    player_score
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Day2;
+    use common::{InputSource, Solver};
+
+    #[test]
+    fn example_answers() {
+        common::testing::assert_example::<Day2>(15, 12);
+    }
+
+    #[test]
+    fn validate_lists_every_bad_line() {
+        let errors: Vec<String> = Day2::validate("A Y\nD X\nB Y\nC\nA  Z\nC Z \n")
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(4, errors.len());
+        assert!(errors[0].contains("line 2, column 1"), "{}", errors[0]);
+        assert!(errors[1].contains("line 4, column 2"), "{}", errors[1]);
+        assert!(errors[2].contains("line 5, column 3"), "{}", errors[2]);
+        assert!(errors[3].contains("line 6, column 4"), "{}", errors[3]);
+    }
+
+    #[test]
+    fn malformed_rounds_are_reported() {
+        let error = |input: &str| Day2::parse(input).err().unwrap().to_string();
+
+        assert_eq!(
+            "line 2, column 2: expected a space (`B`)",
+            error("A Y\nB\n")
+        );
+        assert_eq!(
+            "line 2, column 1: expected A, B or C (``)",
+            error("A Y\n\n")
+        );
+        assert_eq!(
+            "line 1, column 3: expected X, Y or Z, found `W` (`A W`)",
+            error("A W\n")
+        );
+    }
+
+    #[test]
+    fn streaming_reports_the_bad_line() {
+        let lines = InputSource::Embedded("A Y\nB X\nC\n").lines().unwrap();
+
+        assert_eq!(
+            "line 3, column 2: expected a space (`C`)",
+            Day2::stream(lines).unwrap_err().to_string()
+        );
+    }
+}
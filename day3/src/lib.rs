@@ -0,0 +1,327 @@
+use common::{Answer, Error, Line, Solver};
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let lines: Vec<Line> = common::numbered_lines(input).collect();
+        for line in &lines {
+            check_items(line)?;
+        }
+        if let Some(group) = lines.chunks(3).find(|group| group.len() < 3) {
+            return Err(short_group(group));
+        }
+        Ok(lines.iter().map(|line| line.text.to_string()).collect())
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+
+    fn validate(input: &str) -> Vec<Error> {
+        let lines: Vec<Line> = common::numbered_lines(input).collect();
+        let mut errors: Vec<Error> = lines
+            .iter()
+            .filter_map(|line| validate_rucksack(line).err())
+            .collect();
+
+        for group in lines.chunks(3) {
+            if group.len() < 3 {
+                errors.push(short_group(group));
+            } else if group.iter().all(|line| validate_rucksack(line).is_ok()) {
+                let badges = get_common_items(group[0].text, group[1].text)
+                    .into_iter()
+                    .collect::<String>();
+                let badges = get_common_items(&badges, group[2].text);
+                if badges.len() != 1 {
+                    errors.push(group[0].error(format!(
+                        "group shares {} item types, expected one badge",
+                        badges.len()
+                    )));
+                }
+            }
+        }
+        errors.sort_by_key(|error| match error {
+            Error::Parse { line, .. } => *line,
+            _ => 0,
+        });
+        errors
+    }
+}
+
+/// A rucksack is an even number of letters, with exactly one item type in both halves.
+fn validate_rucksack(line: &Line) -> Result<(), Error> {
+    check_items(line)?;
+
+    let (cmp_1, cmp_2) = split_rucksack(line.text);
+    match get_common_items(cmp_1, cmp_2).len() {
+        1 => Ok(()),
+        n => Err(line.error(format!("compartments share {} item types, expected one", n))),
+    }
+}
+
+/// What solving relies on, the items are letters and split into two equal compartments.
+fn check_items(line: &Line) -> Result<(), Error> {
+    if line.text.is_empty() {
+        return Err(line.error("empty rucksack"));
+    }
+    if let Some(idx) = line.text.chars().position(|c| !c.is_ascii_alphabetic()) {
+        return Err(line.error_at(idx + 1, "items must be letters"));
+    }
+    if !line.text.len().is_multiple_of(2) {
+        return Err(line.error("odd number of items, the compartments must be the same size"));
+    }
+    Ok(())
+}
+
+fn short_group(group: &[Line]) -> Error {
+    group[group.len() - 1].error("the last group has fewer than three rucksacks")
+}
+
+fn part_one(input: &[String]) -> usize {
+    let mut total = 0;
+
+    for sack in input {
+        let (cmp_1, cmp_2) = split_rucksack(sack.as_str());
+        let common = get_common_items(cmp_1, cmp_2);
+        total += sum_item_priorities(common);
+    }
+    total
+}
+
+fn part_two(input: &[String]) -> usize {
+    let mut total = 0;
+
+    for i in (0..input.len()).step_by(3) {
+        let e1_e2_common = get_common_items(input[i].as_str(), input[i + 1].as_str())
+            .into_iter()
+            .collect::<String>();
+        let e1e2_e3_common = get_common_items(e1_e2_common.as_str(), input[i + 2].as_str());
+
+        total += sum_item_priorities(e1e2_e3_common);
+    }
+
+    total
+}
+
+fn sum_item_priorities(items: Vec<char>) -> usize {
+    let r = items.iter().map(get_priority).sum::<usize>();
+    r
+}
+
+fn get_common_items(cmp_1: &str, cmp_2: &str) -> Vec<char> {
+    let mut common_items: Vec<char> = vec![];
+    let mut cmp_arr: [usize; 26 * 2] = [0; 26 * 2];
+
+    for item in cmp_1.chars() {
+        cmp_arr[get_priority(&item) - 1] = 1;
+    }
+
+    for item in cmp_2.chars() {
+        // TODO: This contains check is sub-optimal, hashmap/set or better solution in general
+        if cmp_arr.get(get_priority(&item) - 1).unwrap_or(&0) == &1_usize
+            && !common_items.contains(&item)
+        {
+            common_items.push(item);
+        }
+    }
+
+    common_items
+}
+
+fn split_rucksack(sack: &str) -> (&str, &str) {
+    sack.split_at(sack.len() / 2)
+}
+
+fn get_priority(item: &char) -> usize {
+    // TODO: This could be statically defined for performance
+    let alphabet: Vec<char> = ('a'..='z').chain('A'..='Z').collect::<Vec<char>>();
+    alphabet
+        .iter()
+        .position(|&x| x == *item)
+        .expect("items are checked to be letters while parsing")
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_common_items, get_priority, part_one, part_two, split_rucksack, Day3};
+    use common::generate::{self, Rng};
+    use common::Solver;
+    use std::collections::HashSet;
+
+    fn reference_priority(item: char) -> usize {
+        match item {
+            'a'..='z' => item as usize - 'a' as usize + 1,
+            _ => item as usize - 'A' as usize + 27,
+        }
+    }
+
+    fn item_set(items: &str) -> HashSet<char> {
+        items.chars().collect()
+    }
+
+    #[test]
+    fn example_split_sacks() {
+        let example = common::testing::example(3);
+        let inputs: Vec<&str> = example.lines().collect();
+        let expected_results: Vec<(&str, &str)> = vec![
+            ("vJrwpWtwJgWr", "hcsFMMfFFhFp"),
+            ("jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"),
+            ("PmmdzqPrV", "vPwwTWBwg"),
+            ("wMqvLMZHhHMvwLH", "jbvcjnnSBnvTQFn"),
+            ("ttgJtRGJ", "QctTZtZT"),
+            ("CrZsJsPPZsGz", "wwsLwLmpwMDw"),
+        ];
+
+        for (idx, input) in inputs.iter().enumerate() {
+            let expected = expected_results.get(idx).unwrap().to_owned();
+            let actual = split_rucksack(input);
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn example_item_priority() {
+        let inputs: Vec<char> = vec!['a', 'm', 'z', 'A', 'M', 'Z'];
+        let expected_results: Vec<usize> = vec![1, 13, 26, 27, 39, 52];
+
+        for (idx, input) in inputs.iter().enumerate() {
+            let expected = expected_results.get(idx).unwrap().to_owned();
+            let actual = get_priority(input);
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn can_get_common_items() {
+        let inputs: Vec<(&str, &str)> = vec![
+            ("abcDefGhijK", "ajKQWERTYZX"),
+            ("abcd", "abcd"),
+            ("abcd", "qwer"),
+        ];
+        let expected_results: Vec<Vec<char>> =
+            vec![vec!['a', 'j', 'K'], vec!['a', 'b', 'c', 'd'], vec![]];
+
+        for (idx, (left_cmp, right_cmp)) in inputs.iter().enumerate() {
+            let expected = expected_results.get(idx).unwrap().to_owned();
+            let actual = get_common_items(left_cmp, right_cmp);
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn example_answers() {
+        common::testing::assert_example::<Day3>(157, 70);
+    }
+
+    #[test]
+    fn common_items_match_set_intersection() {
+        let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let sack = |rng: &mut Rng| -> String {
+            (0..rng.range(0..=12)).map(|_| *rng.pick(&items)).collect()
+        };
+        let mut rng = Rng::new(3);
+
+        for _ in 0..5000 {
+            let (left, right) = (sack(&mut rng), sack(&mut rng));
+
+            let mut actual = get_common_items(&left, &right);
+            let mut expected: Vec<char> = item_set(&left)
+                .intersection(&item_set(&right))
+                .copied()
+                .collect();
+            actual.sort();
+            expected.sort();
+
+            assert_eq!(expected, actual, "`{}` and `{}`", left, right);
+        }
+    }
+
+    #[test]
+    fn answers_match_reference_on_generated_inputs() {
+        for seed in 0..500 {
+            let input = Day3::parse(&generate::rucksacks(&mut Rng::new(seed), 4)).unwrap();
+
+            let expected_one: usize = input
+                .iter()
+                .map(|sack| {
+                    let (left, right) = sack.split_at(sack.len() / 2);
+                    let shared = &item_set(left) & &item_set(right);
+                    shared.into_iter().map(reference_priority).sum::<usize>()
+                })
+                .sum();
+            let expected_two: usize = input
+                .chunks(3)
+                .map(|group| {
+                    let shared =
+                        &(&item_set(&group[0]) & &item_set(&group[1])) & &item_set(&group[2]);
+                    shared.into_iter().map(reference_priority).sum::<usize>()
+                })
+                .sum();
+
+            assert_eq!(expected_one, part_one(&input), "seed {}", seed);
+            assert_eq!(expected_two, part_two(&input), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn validate_lists_every_bad_rucksack() {
+        let input = "abcA\nab1b\nabc\nAbAb\n\nxyzx\nbZcZ\n";
+        let errors: Vec<String> = Day3::validate(input)
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(6, errors.len(), "{:?}", errors);
+        assert!(
+            errors[0].contains("line 1: compartments share 0"),
+            "{}",
+            errors[0]
+        );
+        assert!(errors[1].contains("line 2, column 3"), "{}", errors[1]);
+        assert!(errors[2].contains("line 3: odd number"), "{}", errors[2]);
+        assert!(
+            errors[3].contains("line 4: compartments share 2"),
+            "{}",
+            errors[3]
+        );
+        assert!(errors[4].contains("line 5: empty"), "{}", errors[4]);
+        assert!(
+            errors[5].contains("line 7: the last group"),
+            "{}",
+            errors[5]
+        );
+    }
+
+    #[test]
+    fn validate_checks_group_badges() {
+        assert!(Day3::validate(&common::testing::example(3)).is_empty());
+
+        let errors = Day3::validate("abcb\nbdab\nbfab\n");
+        assert_eq!(1, errors.len());
+        assert!(errors[0].to_string().contains("line 1: group shares 2"));
+    }
+
+    #[test]
+    fn malformed_rucksacks_are_reported() {
+        let error = |input: &str| Day3::parse(input).err().unwrap().to_string();
+
+        assert_eq!(
+            "line 2, column 3: items must be letters (`ab1b`)",
+            error("abcb\nab1b\nbfab\n")
+        );
+        assert!(error("abcb\nabc\nbfab\n").starts_with("line 2: odd number of items"));
+        assert_eq!(
+            "line 4: the last group has fewer than three rucksacks (`abab`)",
+            error("abcb\nbdab\nbfab\nabab\n")
+        );
+    }
+}
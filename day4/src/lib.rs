@@ -0,0 +1,220 @@
+use common::{Answer, Error, Line, ReadLines, Solver};
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<SectionPair>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        common::numbered_lines(input)
+            .map(|line| SectionPair::parse(&line))
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        part_two(input).into()
+    }
+
+    fn stream(lines: ReadLines) -> Result<[Answer; 2], Error> {
+        let (mut contained_pairs, mut overlap_pairs) = (0, 0);
+        for line in lines {
+            let pair = SectionPair::parse(&line?.line())?;
+            contained_pairs += usize::from(pair.contained());
+            overlap_pairs += usize::from(pair.overlaps());
+        }
+
+        Ok([contained_pairs.into(), overlap_pairs.into()])
+    }
+
+    fn validate(input: &str) -> Vec<Error> {
+        common::numbered_lines(input)
+            .filter_map(|line| {
+                SectionPair::parse(&line)
+                    .and_then(|pair| pair.check(&line))
+                    .err()
+            })
+            .collect()
+    }
+}
+
+pub struct SectionPair {
+    left_min: usize,
+    left_max: usize,
+    right_min: usize,
+    right_max: usize,
+}
+
+impl SectionPair {
+    fn parse(line: &Line) -> Result<Self, Error> {
+        let (left_min, left_max, right_min, right_max) = line.scan("{}-{},{}-{}")?;
+        Ok(SectionPair {
+            left_min,
+            left_max,
+            right_min,
+            right_max,
+        })
+    }
+
+    /// Ranges must be given low to high, and sections are numbered from 1.
+    fn check(&self, line: &Line) -> Result<(), Error> {
+        let ranges = [
+            ("left", self.left_min, self.left_max),
+            ("right", self.right_min, self.right_max),
+        ];
+        for (side, min, max) in ranges {
+            if min == 0 {
+                return Err(line.error(format!("{} range starts at section 0", side)));
+            }
+            if min > max {
+                return Err(line.error(format!(
+                    "{} range {}-{} ends before it starts",
+                    side, min, max
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn contained(&self) -> bool {
+        (self.left_min >= self.right_min && self.left_max <= self.right_max)
+            || (self.right_min >= self.left_min && self.right_max <= self.left_max)
+    }
+
+    fn overlaps(&self) -> bool {
+        self.left_max >= self.right_min && self.left_min <= self.right_max
+    }
+}
+
+fn part_one(input: &[SectionPair]) -> usize {
+    input.iter().filter(|pair| pair.contained()).count()
+}
+
+fn part_two(input: &[SectionPair]) -> usize {
+    input.iter().filter(|pair| pair.overlaps()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{part_one, part_two, Day4, SectionPair};
+    use common::generate::{self, Rng};
+    use common::Solver;
+    use std::collections::HashSet;
+
+    /// Every section in both ranges, checked by brute force membership.
+    fn sections(pair: &SectionPair) -> (HashSet<usize>, HashSet<usize>) {
+        (
+            (pair.left_min..=pair.left_max).collect(),
+            (pair.right_min..=pair.right_max).collect(),
+        )
+    }
+
+    fn reference_counts(input: &[SectionPair]) -> (usize, usize) {
+        let (mut contained, mut overlapping) = (0, 0);
+        for pair in input {
+            let (left, right) = sections(pair);
+            if left.is_subset(&right) || right.is_subset(&left) {
+                contained += 1;
+            }
+            if !left.is_disjoint(&right) {
+                overlapping += 1;
+            }
+        }
+        (contained, overlapping)
+    }
+
+    #[test]
+    fn example_answers() {
+        common::testing::assert_example::<Day4>(2, 4);
+    }
+
+    #[test]
+    fn answers_match_reference_on_generated_inputs() {
+        for seed in 0..200 {
+            let input = Day4::parse(&generate::section_pairs(&mut Rng::new(seed), 50)).unwrap();
+
+            let (contained, overlapping) = reference_counts(&input);
+            assert_eq!(contained, part_one(&input), "seed {}", seed);
+            assert_eq!(overlapping, part_two(&input), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn answers_match_reference_on_small_ranges() {
+        // Few sections make shared ends, single sections and equal ranges common
+        let mut rng = Rng::new(4);
+        let mut range = || {
+            let start = rng.range(1..=5);
+            (start, rng.range(start..=5))
+        };
+        let lines: Vec<String> = (0..5000)
+            .map(|_| {
+                let ((a, b), (c, d)) = (range(), range());
+                format!("{}-{},{}-{}", a, b, c, d)
+            })
+            .collect();
+        let input = Day4::parse(&lines.join("\n")).unwrap();
+
+        assert_eq!(
+            reference_counts(&input),
+            (part_one(&input), part_two(&input))
+        );
+    }
+
+    #[test]
+    fn part_2_extended_test() {
+        let inputs: Vec<String> = vec![
+            "2-4,6-8".to_string(),
+            "2-3,4-5".to_string(),
+            "5-7,7-9".to_string(),
+            "2-8,3-7".to_string(),
+            "6-6,4-6".to_string(),
+            "2-6,4-8".to_string(),
+            "1-1,4-4".to_string(),
+            "1-2,2-3".to_string(),
+            "1-5,2-3".to_string(),
+            "2-3,1-5".to_string(),
+            "8-9,7-8".to_string(),
+            "1-2,11-12".to_string(),
+            "3-99,1-2".to_string(),
+        ];
+
+        assert_eq!(8, part_two(&Day4::parse(&inputs.join("\n")).unwrap()));
+    }
+
+    #[test]
+    fn malformed_pairs_are_reported() {
+        let error = Day4::parse("2-4,6-8\n2-3;4-5").err().unwrap();
+
+        assert_eq!(
+            "line 2, column 3: expected `,` after this field (`2-3;4-5`)",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn validate_lists_every_bad_pair() {
+        let errors: Vec<String> = Day4::validate("2-4,6-8\n5-3,1-2\n1-2,x-3\n0-1,2-3\n2-8,9-7\n")
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(4, errors.len(), "{:?}", errors);
+        assert!(
+            errors[0].contains("line 2: left range 5-3"),
+            "{}",
+            errors[0]
+        );
+        assert!(errors[1].contains("line 3, column 5"), "{}", errors[1]);
+        assert!(errors[2].contains("line 4: left range starts at section 0"));
+        assert!(
+            errors[3].contains("line 5: right range 9-7"),
+            "{}",
+            errors[3]
+        );
+    }
+}